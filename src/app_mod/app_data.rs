@@ -0,0 +1,299 @@
+use crate::prelude::*;
+use sdl2::{keyboard::Keycode, render::TextureCreator, video::WindowContext, event::Event, mouse::MouseState, EventPump};
+use ab_glyph::FontVec;
+
+
+
+pub struct ProgramData<'a> {
+
+    pub start_instant: Instant,
+    pub exit: bool,
+
+    pub camera: Camera,
+    pub selected_entity: EntitySelection,
+    pub keys_pressed: HashMap<Keycode, ()>,
+
+    pub render_data: RenderData<'a>,
+
+    pub world: World,
+    pub paused: bool,
+    pub single_step_requested: bool,
+    pub time_scale: f64,
+    pub fixed_timestep: Option<FixedTimestep>,
+    pub settings_watcher: SettingsWatcher,
+    pub status_message: Option<(String, Instant)>, // shown in the top right for STATUS_MESSAGE_DURATION seconds
+    pub profile_frame_count: Option<u64>,
+
+}
+
+impl<'a> ProgramData<'a> {
+
+    pub fn new (render_data: RenderData<'a>, world: World, camera: Camera, fixed_timestep: Option<FixedTimestep>, settings_watcher: SettingsWatcher) -> Self {
+        Self {
+
+            start_instant: Instant::now(),
+            exit: false,
+
+            camera,
+            selected_entity: EntitySelection::None,
+            keys_pressed: HashMap::new(),
+
+            render_data,
+
+            world,
+            paused: false,
+            single_step_requested: false,
+            time_scale: 1.,
+            fixed_timestep,
+            settings_watcher,
+            status_message: None,
+            profile_frame_count: None,
+
+        }
+    }
+
+    pub fn key_is_pressed (&self, keycode: Keycode) -> bool {
+        self.keys_pressed.contains_key(&keycode)
+    }
+
+    pub fn show_status_message (&mut self, message: String) {
+        self.status_message = Some((message, Instant::now()));
+    }
+
+}
+
+
+
+// usage: cell_engine [--seed <seed>] [--size <width>x<height>] [--max-entities <count>] [--wrap] [--fixed-dt <seconds>] [--settings <path>] [--load <path>] [--profile <frame count>]
+pub struct ProgramArgs {
+    pub seed: u64,
+    pub world_size: WorldSize,
+    pub max_entities_count: Option<usize>,
+    pub wrap: bool,
+    pub fixed_dt: Option<f64>,
+    pub settings_path: PathBuf,
+    pub load_path: Option<PathBuf>,
+    pub profile_frame_count: Option<u64>,
+}
+
+impl ProgramArgs {
+    pub fn from_args (mut args: impl Iterator<Item = String>) -> Result<Self, ProgramError> {
+        let mut output = Self {
+            seed: 0,
+            world_size: WorldSize::default(),
+            max_entities_count: None,
+            wrap: false,
+            fixed_dt: None,
+            settings_path: SimSettings::get_default_path(),
+            load_path: None,
+            profile_frame_count: None,
+        };
+        while let Some(arg) = args.next() {
+            match &*arg {
+                "--seed" => output.seed = fns::parse_arg_value(&arg, args.next())?,
+                "--size" => output.world_size = fns::parse_arg_value(&arg, args.next())?,
+                "--max-entities" => output.max_entities_count = Some(fns::parse_arg_value(&arg, args.next())?),
+                "--wrap" => output.wrap = true,
+                "--fixed-dt" => output.fixed_dt = Some(fns::parse_dt_arg_value(&arg, args.next())?),
+                "--settings" => output.settings_path = fns::parse_arg_value(&arg, args.next())?,
+                "--load" => output.load_path = Some(fns::parse_arg_value(&arg, args.next())?),
+                "--profile" => output.profile_frame_count = Some(fns::parse_arg_value(&arg, args.next())?),
+                _ => return Err(ProgramError::String(format!("Unknown argument \"{arg}\""))),
+            }
+        }
+        Ok(output)
+    }
+}
+
+
+
+pub type GlyphCache<'a> = HashMap<HashableGlyph, GlyphTexture<'a>>;
+
+
+
+
+
+pub struct RenderData<'a> {
+    pub textures: ProgramTextures<'a>,
+    pub texture_creator: &'a TextureCreator<WindowContext>,
+    pub font: FontVec,
+    pub glyph_cache: GlyphCache<'a>,
+}
+
+impl<'a> RenderData<'a> {
+    pub fn new (textures: ProgramTextures<'a>, font: FontVec, texture_creator: &'a TextureCreator<WindowContext>) -> Self {
+        Self {
+            textures,
+            texture_creator,
+            font,
+            glyph_cache: HashMap::new(),
+        }
+    }
+}
+
+
+
+#[derive(PartialEq)]
+pub enum EntitySelection {
+    None,
+    Cell (EntityID),
+    Food (EntityID),
+}
+
+
+
+#[derive(Hash, Eq, PartialEq)]
+pub struct HashableGlyph {
+    pub glyph_id: GlyphId,
+    pub scale_x: u32,
+    pub scale_y: u32,
+}
+
+impl HashableGlyph {
+    pub fn from_glyph (glyph: &Glyph) -> Self {
+        Self {
+            glyph_id: glyph.id,
+            scale_x: glyph.scale.x as u32,
+            scale_y: glyph.scale.y as u32,
+        }
+    }
+}
+
+
+
+pub struct GlyphTexture<'a> {
+    pub texture: Texture<'a>,
+    pub origin_x: i32,
+    pub origin_y: i32,
+}
+
+
+
+
+
+#[derive(Debug)]
+pub struct EventsData {
+    pub list: Vec<Event>,
+    pub mouse_state: MouseState,
+}
+
+impl EventsData {
+
+    pub fn from_event_pump (event_pump: &mut EventPump) -> Self {
+        Self {
+            list: event_pump.poll_iter().filter(Self::filter_event).collect(),
+            mouse_state: event_pump.mouse_state(),
+        }
+    }
+
+    pub fn filter_event (event: &Event) -> bool {
+        matches!(event,
+            Event::Quit {..} |
+            Event::KeyDown {..} |
+            Event::KeyUp {..} |
+            Event::MouseWheel {..} |
+            Event::MouseButtonDown {..}
+        )
+    }
+
+}
+
+
+
+
+
+pub struct ProgramTextures<'a> {
+    pub ground: Texture<'a>,
+    pub black_ground: Texture<'a>,
+    pub food: Texture<'a>,
+    pub circle: Texture<'a>,
+}
+
+
+
+pub trait EntityTexture {
+    fn get_texture<'a> (&self, textures: &'a ProgramTextures<'a>) -> &'a Texture<'a>;
+}
+
+impl EntityTexture for Cell {
+    fn get_texture<'a> (&self, textures: &'a ProgramTextures<'a>) -> &'a Texture<'a> {
+        &textures.circle
+    }
+}
+
+impl EntityTexture for Food {
+    fn get_texture<'a> (&self, textures: &'a ProgramTextures<'a>) -> &'a Texture<'a> {
+        &textures.food
+    }
+}
+
+
+
+
+
+#[derive(Debug)]
+pub struct Area {
+    pub screen_size: (u32, u32),
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Area {
+
+    pub fn new (screen_size: (u32, u32)) -> Self {
+        Self {
+            screen_size,
+            x: 0.,
+            y: 0.,
+            width: 1.,
+            height: 1.,
+        }
+    }
+
+    pub fn get_basic_sub_area (&self, x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            screen_size: self.screen_size,
+            x: self.x + x * self.width,
+            y: self.y + y * self.height,
+            width:  width  * self.width,
+            height: height * self.height,
+        }
+    }
+
+    pub fn get_sub_area (&self, x: f64, y: f64, width: f64, height: f64, natural_x: f64, natural_width: f64) -> Self {
+        let aspect_ratio = (self.screen_size.0 as f64 / self.screen_size.1 as f64) * (self.width / self.height);
+        Self {
+            screen_size: self.screen_size,
+            x: self.x + x * self.width + natural_x * self.width / aspect_ratio,
+            y: self.y + y * self.height,
+            width:  width  * self.width + natural_width * self.width / aspect_ratio,
+            height: height * self.height,
+        }
+    }
+
+    pub fn get_point (&self, x: f64, y: f64, natural_x: f64) -> (i32, i32) {
+        let aspect_ratio = (self.screen_size.0 as f64 / self.screen_size.1 as f64) * (self.width / self.height);
+        let mut point_x = self.x + x * self.width + natural_x * self.width / aspect_ratio;
+        let mut point_y = self.y + y * self.height;
+        point_x *= self.screen_size.0 as f64;
+        point_y *= self.screen_size.1 as f64;
+        (point_x.round() as i32, point_y.round() as i32)
+    }
+
+    pub fn to_rect (&self) -> Rect {
+        let x = self.x * self.screen_size.0 as f64;
+        let y = self.y * self.screen_size.1 as f64;
+        let width  = self.width  * self.screen_size.0 as f64;
+        let height = self.height * self.screen_size.1 as f64;
+        let end_x = x + width;
+        let end_y = y + height;
+        let final_x = x.round() as i32;
+        let final_y = y.round() as i32;
+        let final_width = (end_x.round() as i32) - final_x;
+        let final_height = (end_y.round() as i32) - final_y;
+        Rect::new(final_x, final_y, final_width as u32, final_height as u32)
+    }
+
+}
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use sdl2::{keyboard::Keycode, render::WindowCanvas};
+
+
+
+pub fn update (program_data: &mut ProgramData, canvas: &WindowCanvas, events_data: EventsData, frame_time: f64) -> Result<(), ProgramError> {
+    let dt = frame_time.min(MAX_FRAME_DT);
+    
+    events::process_events(program_data, events_data, canvas)?;
+
+    move_camera(program_data, dt);
+
+    if let Some(settings) = program_data.settings_watcher.check_for_changes() {
+        program_data.world.settings = settings;
+        program_data.show_status_message(String::from("Reloaded settings"));
+    }
+
+    step_world(program_data, frame_time);
+    clear_removed_selection(program_data);
+
+    // finish profiling
+    if let Some(profile_frame_count) = program_data.profile_frame_count {
+        if program_data.world.frame_count >= profile_frame_count {
+            if let Some(profile_data) = &program_data.world.profile_data {
+                println!("{profile_data}");
+            }
+            program_data.exit = true;
+        }
+    }
+
+    Ok(())
+}
+
+
+
+pub fn step_world (program_data: &mut ProgramData, frame_time: f64) {
+
+    if program_data.paused {
+        if !program_data.single_step_requested {return;}
+        program_data.single_step_requested = false;
+        let dt = program_data.fixed_timestep.as_ref().map_or(SINGLE_STEP_DT, |fixed_timestep| fixed_timestep.dt);
+        program_data.world.step(dt);
+        return;
+    }
+
+    let time_scale = program_data.time_scale;
+    match &mut program_data.fixed_timestep {
+        Some(fixed_timestep) => {
+            let step_count = fixed_timestep.get_step_count(frame_time, time_scale);
+            for _ in 0..step_count {
+                program_data.world.step(fixed_timestep.dt);
+            }
+        }
+        None => {
+            // high speeds are split into sub-steps so that dt never goes above MAX_FRAME_DT
+            let total_time = frame_time.min(MAX_FRAME_DT) * time_scale;
+            let step_count = (total_time / MAX_FRAME_DT).ceil().max(1.);
+            for _ in 0..step_count as u32 {
+                program_data.world.step(total_time / step_count);
+            }
+        }
+    }
+
+}
+
+
+
+// the selected entity can be removed by any step (eggs hatching, cells dying, ...)
+pub fn clear_removed_selection (program_data: &mut ProgramData) {
+    let is_removed = match program_data.selected_entity {
+        EntitySelection::None => false,
+        EntitySelection::Cell (entity_id) => program_data.world.cells.get(entity_id).is_none(),
+        EntitySelection::Food (entity_id) => program_data.world.food.get(entity_id).is_none(),
+    };
+    if is_removed {
+        program_data.selected_entity = EntitySelection::None;
+    }
+}
+
+
+
+pub fn change_time_scale (program_data: &mut ProgramData, multiplier: f64) {
+    program_data.time_scale = (program_data.time_scale * multiplier).clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+}
+
+
+
+pub fn move_camera(program_data: &mut ProgramData, dt: f64) {
+    let current_speed = CAMERA_SPEED / program_data.camera.zoom * dt;
+
+    if program_data.key_is_pressed(Keycode::W) {
+        program_data.camera.y -= current_speed;
+    }
+    if program_data.key_is_pressed(Keycode::S) {
+        program_data.camera.y += current_speed;
+    }
+
+    if program_data.key_is_pressed(Keycode::A) {
+        program_data.camera.x -= current_speed;
+    }
+    if program_data.key_is_pressed(Keycode::D) {
+        program_data.camera.x += current_speed;
+    }
+
+}
@@ -28,6 +28,18 @@ pub fn handle_key_down (program_data: &mut ProgramData, keycode: Keycode) {
 
         Keycode::Escape => handle_esc_pressed(program_data),
 
+        Keycode::Space => program_data.paused = !program_data.paused,
+
+        Keycode::Period if program_data.paused => program_data.single_step_requested = true,
+
+        Keycode::Equals | Keycode::KpPlus => app_update::change_time_scale(program_data, 2.),
+
+        Keycode::Minus | Keycode::KpMinus => app_update::change_time_scale(program_data, 0.5),
+
+        Keycode::F5 => quicksave(program_data),
+
+        Keycode::F9 => quickload(program_data),
+
         Keycode::W if program_data.key_is_pressed(Keycode::LCtrl) => {
             program_data.exit = true;
         }
@@ -50,7 +62,6 @@ pub fn handle_esc_pressed (program_data: &mut ProgramData) {
     // remove selected entity
     if program_data.selected_entity != EntitySelection::None {
         program_data.selected_entity = EntitySelection::None;
-        return;
     }
 
 }
@@ -58,6 +69,36 @@ pub fn handle_esc_pressed (program_data: &mut ProgramData) {
 
 
 
+pub fn get_quicksave_path() -> PathBuf {
+    let mut path = fns::get_program_dir();
+    path.push(QUICKSAVE_FILE_NAME);
+    path
+}
+
+pub fn quicksave (program_data: &mut ProgramData) {
+    let path = get_quicksave_path();
+    match save_world(&program_data.world, &program_data.camera, &path) {
+        Ok(()) => program_data.show_status_message(format!("Saved world to {path:?}")),
+        Err(error) => program_data.show_status_message(format!("Could not save world to {path:?}: {error:?}")),
+    }
+}
+
+pub fn quickload (program_data: &mut ProgramData) {
+    let path = get_quicksave_path();
+    match load_world(&path, program_data.world.settings.clone()) {
+        Ok((world, camera)) => {
+            program_data.world = world;
+            program_data.camera = camera;
+            program_data.selected_entity = EntitySelection::None;
+            program_data.show_status_message(format!("Loaded world from {path:?}"));
+        }
+        Err(error) => program_data.show_status_message(format!("Could not load world from {path:?}: {error:?}")),
+    }
+}
+
+
+
+
 
 pub fn process_mouse_click (program_data: &mut ProgramData, mouse_button: MouseButton, x: i32, y: i32, canvas: &WindowCanvas) -> Result<(), ProgramError> {
     
@@ -97,13 +138,19 @@ pub enum ScreenItem {
 
 pub fn get_screen_item_at_pos (x: i32, y: i32, program_data: &ProgramData, canvas: &WindowCanvas) -> Result<ScreenItem, ProgramError> {
 
-    let map_pos = fns::convert_screen_to_grid((x, y), &program_data.camera, canvas.output_size()?);
+    let mut map_pos = fns::convert_screen_to_grid((x, y), &program_data.camera, canvas.output_size()?);
+    let world_size = program_data.world.size;
+    if world_size.wraps() {
+        map_pos = world_size.wrap_pos(map_pos);
+    } else if map_pos.0 < 0. || map_pos.1 < 0. || map_pos.0 >= world_size.width as f64 || map_pos.1 >= world_size.height as f64 {
+        return Ok(ScreenItem::None);
+    }
     let grid_pos = (map_pos.0 as usize, map_pos.1 as usize);
 
-    if let Some(entity_id) = get_entity_at_pos(grid_pos, map_pos, &program_data.cells) {
+    if let Some(entity_id) = get_entity_at_pos(grid_pos, map_pos, &program_data.world.cells) {
         return Ok(ScreenItem::Cell(entity_id));
     }
-    if let Some(entity_id) = get_entity_at_pos(grid_pos, map_pos, &program_data.food) {
+    if let Some(entity_id) = get_entity_at_pos(grid_pos, map_pos, &program_data.world.food) {
         return Ok(ScreenItem::Food(entity_id));
     }
 
@@ -116,7 +163,7 @@ pub fn get_entity_at_pos<T: Entity + AsRef<RawEntity>> (grid_pos: (usize, usize)
     let entity_ids = fns::get_entity_ids_near_pos(grid_pos, entities);
     for current_entity_id in entity_ids {
         let raw_entity = entities.master_list[current_entity_id.0].0.as_ref().unwrap().as_ref();
-        let dist_vec = (map_pos.0 - raw_entity.x, map_pos.1 - raw_entity.y);
+        let dist_vec = entities.size.get_pos_change((raw_entity.x, raw_entity.y), map_pos);
         let dist_vec = (dist_vec.0 / raw_entity.width, dist_vec.1 / raw_entity.height);
         let dist_to_cell_center = fns::vec_len(dist_vec);
         if dist_to_cell_center <= 0.5 {
@@ -0,0 +1,3 @@
+pub mod app_data;
+pub mod app_update;
+pub mod events;
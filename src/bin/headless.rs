@@ -0,0 +1,105 @@
+// Runs the simulation without a window for a fixed number of ticks, then prints a summary
+// usage: headless [--ticks <count>] [--dt <seconds>] [--seed <seed>] [--size <width>x<height>] [--max-entities <count>] [--wrap] [--settings <path>] [--load <path>] [--save <path>] [--profile]
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DEFAULT_TICK_COUNT: u64 = 1000;
+const DEFAULT_DT: f64 = 0.016;
+
+
+
+
+
+pub struct HeadlessArgs {
+    pub tick_count: u64,
+    pub dt: f64,
+    pub seed: u64,
+    pub world_size: WorldSize,
+    pub max_entities_count: Option<usize>,
+    pub wrap: bool,
+    pub settings_path: PathBuf,
+    pub load_path: Option<PathBuf>,
+    pub save_path: Option<PathBuf>,
+    pub profile: bool,
+}
+
+impl HeadlessArgs {
+    pub fn from_args (mut args: impl Iterator<Item = String>) -> Result<Self, ProgramError> {
+        let mut output = Self {
+            tick_count: DEFAULT_TICK_COUNT,
+            dt: DEFAULT_DT,
+            seed: 0,
+            world_size: WorldSize::default(),
+            max_entities_count: None,
+            wrap: false,
+            settings_path: SimSettings::get_default_path(),
+            load_path: None,
+            save_path: None,
+            profile: false,
+        };
+        while let Some(arg) = args.next() {
+            match &*arg {
+                "--ticks" => output.tick_count = fns::parse_arg_value(&arg, args.next())?,
+                "--dt" => output.dt = fns::parse_dt_arg_value(&arg, args.next())?,
+                "--seed" => output.seed = fns::parse_arg_value(&arg, args.next())?,
+                "--size" => output.world_size = fns::parse_arg_value(&arg, args.next())?,
+                "--max-entities" => output.max_entities_count = Some(fns::parse_arg_value(&arg, args.next())?),
+                "--wrap" => output.wrap = true,
+                "--settings" => output.settings_path = fns::parse_arg_value(&arg, args.next())?,
+                "--load" => output.load_path = Some(fns::parse_arg_value(&arg, args.next())?),
+                "--save" => output.save_path = Some(fns::parse_arg_value(&arg, args.next())?),
+                "--profile" => output.profile = true,
+                _ => return Err(ProgramError::String(format!("Unknown argument \"{arg}\""))),
+            }
+        }
+        Ok(output)
+    }
+}
+
+
+
+
+
+
+pub fn main() -> Result<(), ProgramError> {
+    let args = HeadlessArgs::from_args(std::env::args().skip(1))?;
+
+    let settings = SimSettings::load_or_default(&args.settings_path)?;
+    let (mut world, camera) = match &args.load_path {
+        Some(load_path) => load_world(load_path, settings)?,
+        None => {
+            let mut world_size = args.world_size;
+            if let Some(max_entities_count) = args.max_entities_count {world_size.max_entities_count = max_entities_count;}
+            if args.wrap {world_size.boundary_mode = BoundaryMode::Wrap;}
+            let mut world = World::new(world_size, settings, args.seed);
+            world.add_test_data();
+            (world, Camera::new())
+        }
+    };
+
+    if args.profile {
+        world.profile_data = Some(ProfileData::new());
+    }
+
+    let start_instant = Instant::now();
+    for _ in 0..args.tick_count {
+        world.step(args.dt);
+    }
+    let elapsed = start_instant.elapsed().as_secs_f64();
+
+    println!("{}", world.get_stats());
+    println!("Run time (s): {elapsed}");
+    if let Some(profile_data) = &world.profile_data {
+        println!("\n{profile_data}");
+    }
+
+    if let Some(save_path) = &args.save_path {
+        save_world(&world, &camera, save_path)?;
+    }
+
+    Ok(())
+}
@@ -12,7 +12,13 @@ pub struct Cell {
     pub material: f64,
     pub x_vel: f64,
     pub y_vel: f64,
-    pub connected_cells: Vec<EntityID>,
+    pub connections: Vec<ConnectionID>,
+    pub genome: Option<Arc<Genome>>,
+    pub genome_index: usize, // which of the genome's cells this is, index 0 is the organism's root cell
+    pub organism_id: Option<OrganismID>, // None until update::update_organisms() first sees the cell
+    pub controller_outputs: ControllerOutputs, // from the previous frame, see update::update_controller()
+    pub sensor_value: f64, // only set by sensor cells, and readable by connected cells through their controllers
+    pub signals: SignalValues, // chemical signals, these spread to connected cells and decay over time
     pub raw_cell: RawCell,
     pub entity: RawEntity,
 }
@@ -26,7 +32,13 @@ impl Cell {
             material,
             x_vel: 0.,
             y_vel: 0.,
-            connected_cells: vec!(),
+            connections: vec!(),
+            genome: None,
+            genome_index: 0,
+            organism_id: None,
+            controller_outputs: Controller::get_default_outputs(),
+            sensor_value: 0.,
+            signals: [0.; SIGNAL_CHANNEL_COUNT],
             raw_cell,
             entity: RawEntity::new(x, y, 1., 1.),
         }
@@ -39,30 +51,58 @@ impl Cell {
             material,
             x_vel: vel.0,
             y_vel: vel.1,
-            connected_cells: vec!(),
+            connections: vec!(),
+            genome: None,
+            genome_index: 0,
+            organism_id: None,
+            controller_outputs: Controller::get_default_outputs(),
+            sensor_value: 0.,
+            signals: [0.; SIGNAL_CHANNEL_COUNT],
             raw_cell,
             entity: RawEntity::new(pos.0, pos.1, 1., 1.),
         }
     }
-    pub fn pos_change_to (&self, other: &Cell) -> (f64, f64) {
-        (other.entity.x - self.entity.x, other.entity.y - self.entity.y)
+    pub fn pos_change_to (&self, other: &Cell, world_size: &WorldSize) -> (f64, f64) {
+        world_size.get_pos_change((self.entity.x, self.entity.y), (other.entity.x, other.entity.y))
+    }
+    pub fn get_activity (&self) -> f64 {
+        self.controller_outputs[CONTROLLER_OUTPUT_ACTIVITY]
+    }
+    pub fn get_contraction (&self) -> f64 {
+        self.raw_cell.get_contraction() * self.get_activity()
+    }
+    // includes the energy stored in fat & egg cells
+    pub fn get_total_energy (&self) -> f64 {
+        match &self.raw_cell {
+            RawCell::Fat (fat_cell_data) => self.energy + fat_cell_data.extra_energy,
+            RawCell::Egg (egg_cell_data) => self.energy + egg_cell_data.stored_energy,
+            _ => self.energy,
+        }
+    }
+    // includes the material stored in fat & egg cells
+    pub fn get_total_material (&self) -> f64 {
+        match &self.raw_cell {
+            RawCell::Fat (fat_cell_data) => self.material + fat_cell_data.extra_material,
+            RawCell::Egg (egg_cell_data) => self.material + egg_cell_data.stored_material,
+            _ => self.material,
+        }
+    }
+    pub fn is_connected_to (&self, other_cell_id: EntityID, connections: &ConnectionContainer) -> bool {
+        self.connections.iter().any(|&connection_id| {
+            let cell_ids = connections.get(connection_id).unwrap().cell_ids;
+            cell_ids.0 == other_cell_id || cell_ids.1 == other_cell_id
+        })
     }
     pub fn vel_change_to(&self, other: &Cell) -> (f64, f64) {
         (other.x_vel - self.x_vel, other.y_vel - self.y_vel)
     }
-    pub fn distance_to (&self, other: &Cell) -> f64 {
-        let (self_x, self_y) = (self.entity.x, self.entity.y);
-        let (other_x, other_y) = (other.entity.x, other.entity.y);
-        let (dx, dy) = (other_x - self_x, other_y - self_y);
+    pub fn distance_to (&self, other: &Cell, world_size: &WorldSize) -> f64 {
+        let (dx, dy) = self.pos_change_to(other, world_size);
         (dx * dx + dy * dy).sqrt()
     }
 }
 
-impl Entity for Cell {
-    fn get_texture<'a> (&self, textures: &'a ProgramTextures<'a>) -> &'a Texture<'a> {
-        &textures.circle
-    }
-}
+impl Entity for Cell {}
 
 impl AsRef<RawEntity> for Cell {
     fn as_ref(&self) -> &RawEntity {
@@ -80,6 +120,16 @@ impl AsMut<RawEntity> for Cell {
 
 
 
+// the number of chemical signal channels every cell has
+pub const SIGNAL_CHANNEL_COUNT: usize = 2;
+
+pub type SignalValues = [f64; SIGNAL_CHANNEL_COUNT];
+
+
+
+// the number of RawCell variants, see RawCell::new_of_type()
+pub const CELL_TYPE_COUNT: usize = 12;
+
 #[derive(Debug, Clone)]
 pub enum RawCell {
 
@@ -87,22 +137,42 @@ pub enum RawCell {
 
     Photosynthesiser,
 
+    Egg (EggCellData),
+
+    Mouth,
+
+    Flagellum (FlagellumCellData),
+
+    Contractor (ContractorCellData),
+
+    Adhesive,
+
+    FoodSensor (FoodSensorCellData),
+
+    LightSensor,
+
+    ContactSensor,
+
+    Spike (SpikeCellData),
+
+    Armor (ArmorCellData),
+
 }
 
 impl RawCell {
 
-    pub fn new_fat_cell() -> Self {
+    pub fn new_fat_cell (settings: &SimSettings) -> Self {
         Self::Fat (FatCellData {
             extra_energy: 0.0,
             extra_material: 0.0,
-            energy_store_threshold:     CELL_FAT_ENERGY_STORE_THRESHOLD.2,
-            energy_release_threshold:   CELL_FAT_ENERGY_RELEASE_THRESHOLD.2,
-            energy_store_rate:          CELL_FAT_ENERGY_STORE_RATE.2,
-            energy_release_rate:        CELL_FAT_ENERGY_RELEASE_RATE.2,
-            material_store_threshold:   CELL_FAT_MATERIAL_STORE_THRESHOLD.2,
-            material_release_threshold: CELL_FAT_MATERIAL_RELEASE_THRESHOLD.2,
-            material_store_rate:        CELL_FAT_MATERIAL_STORE_RATE.2,
-            material_release_rate:      CELL_FAT_MATERIAL_RELEASE_RATE.2,
+            energy_store_threshold:     settings.fat_energy_store_threshold.default,
+            energy_release_threshold:   settings.fat_energy_release_threshold.default,
+            energy_store_rate:          settings.fat_energy_store_rate.default,
+            energy_release_rate:        settings.fat_energy_release_rate.default,
+            material_store_threshold:   settings.fat_material_store_threshold.default,
+            material_release_threshold: settings.fat_material_release_threshold.default,
+            material_store_rate:        settings.fat_material_store_rate.default,
+            material_release_rate:      settings.fat_material_release_rate.default,
         })
     }
 
@@ -110,6 +180,207 @@ impl RawCell {
         Self::Photosynthesiser
     }
 
+    pub fn new_mouth_cell() -> Self {
+        Self::Mouth
+    }
+
+    pub fn new_flagellum_cell (settings: &SimSettings) -> Self {
+        Self::Flagellum (FlagellumCellData {
+            phase: 0.0,
+            strength:  settings.flagellum_strength.default,
+            frequency: settings.flagellum_frequency.default,
+        })
+    }
+
+    pub fn new_contractor_cell (settings: &SimSettings) -> Self {
+        Self::Contractor (ContractorCellData {
+            phase: 0.0,
+            contraction: settings.contractor_contraction.default,
+            frequency:   settings.contractor_frequency.default,
+        })
+    }
+
+    pub fn new_adhesive_cell() -> Self {
+        Self::Adhesive
+    }
+
+    pub fn new_food_sensor_cell (settings: &SimSettings) -> Self {
+        Self::FoodSensor (FoodSensorCellData {
+            direction: (0.0, 0.0),
+            range: settings.food_sensor_range.default,
+        })
+    }
+
+    pub fn new_light_sensor_cell() -> Self {
+        Self::LightSensor
+    }
+
+    pub fn new_contact_sensor_cell() -> Self {
+        Self::ContactSensor
+    }
+
+    pub fn new_spike_cell (settings: &SimSettings) -> Self {
+        Self::Spike (SpikeCellData {
+            damage: settings.spike_damage.default,
+        })
+    }
+
+    pub fn new_armor_cell (settings: &SimSettings) -> Self {
+        Self::Armor (ArmorCellData {
+            damage_reduction: settings.armor_damage_reduction.default,
+        })
+    }
+
+    pub fn new_egg_cell() -> Self {
+        Self::Egg (EggCellData {
+            stored_energy: 0.0,
+            stored_material: 0.0,
+        })
+    }
+
+    // used for picking random cell types, the indices match get_type_index()
+    pub fn new_of_type (type_index: usize, settings: &SimSettings) -> Self {
+        match type_index {
+            0 => Self::new_fat_cell(settings),
+            1 => Self::new_photosynthesiser_cell(),
+            2 => Self::new_egg_cell(),
+            3 => Self::new_mouth_cell(),
+            4 => Self::new_flagellum_cell(settings),
+            5 => Self::new_contractor_cell(settings),
+            6 => Self::new_adhesive_cell(),
+            7 => Self::new_food_sensor_cell(settings),
+            8 => Self::new_light_sensor_cell(),
+            9 => Self::new_contact_sensor_cell(),
+            10 => Self::new_spike_cell(settings),
+            11 => Self::new_armor_cell(settings),
+            _ => panic!("invalid cell type index: {type_index}"),
+        }
+    }
+
+    pub fn get_type_index (&self) -> usize {
+        match self {
+            Self::Fat (_) => 0,
+            Self::Photosynthesiser => 1,
+            Self::Egg (_) => 2,
+            Self::Mouth => 3,
+            Self::Flagellum (_) => 4,
+            Self::Contractor (_) => 5,
+            Self::Adhesive => 6,
+            Self::FoodSensor (_) => 7,
+            Self::LightSensor => 8,
+            Self::ContactSensor => 9,
+            Self::Spike (_) => 10,
+            Self::Armor (_) => 11,
+        }
+    }
+
+    pub fn get_type_name (&self) -> &'static str {
+        match self {
+            Self::Fat (_) => "fat",
+            Self::Photosynthesiser => "photosynthesiser",
+            Self::Egg (_) => "egg",
+            Self::Mouth => "mouth",
+            Self::Flagellum (_) => "flagellum",
+            Self::Contractor (_) => "contractor",
+            Self::Adhesive => "adhesive",
+            Self::FoodSensor (_) => "food_sensor",
+            Self::LightSensor => "light_sensor",
+            Self::ContactSensor => "contact_sensor",
+            Self::Spike (_) => "spike",
+            Self::Armor (_) => "armor",
+        }
+    }
+
+    // every per-type parameter that can mutate has to be listed here, along with the range it has to stay in
+    pub fn visit_parameters (&mut self, settings: &SimSettings, mut visit_fn: impl FnMut(&'static str, &mut f64, &SettingRange)) {
+        match self {
+            Self::Fat (fat_cell_data) => {
+                visit_fn("energy_store_threshold", &mut fat_cell_data.energy_store_threshold, &settings.fat_energy_store_threshold);
+                visit_fn("energy_release_threshold", &mut fat_cell_data.energy_release_threshold, &settings.fat_energy_release_threshold);
+                visit_fn("energy_store_rate", &mut fat_cell_data.energy_store_rate, &settings.fat_energy_store_rate);
+                visit_fn("energy_release_rate", &mut fat_cell_data.energy_release_rate, &settings.fat_energy_release_rate);
+                visit_fn("material_store_threshold", &mut fat_cell_data.material_store_threshold, &settings.fat_material_store_threshold);
+                visit_fn("material_release_threshold", &mut fat_cell_data.material_release_threshold, &settings.fat_material_release_threshold);
+                visit_fn("material_store_rate", &mut fat_cell_data.material_store_rate, &settings.fat_material_store_rate);
+                visit_fn("material_release_rate", &mut fat_cell_data.material_release_rate, &settings.fat_material_release_rate);
+            }
+            Self::Photosynthesiser => {}
+            Self::Egg (_) => {}
+            Self::Mouth => {}
+            Self::Flagellum (flagellum_cell_data) => {
+                visit_fn("strength", &mut flagellum_cell_data.strength, &settings.flagellum_strength);
+                visit_fn("frequency", &mut flagellum_cell_data.frequency, &settings.flagellum_frequency);
+            }
+            Self::Contractor (contractor_cell_data) => {
+                visit_fn("contraction", &mut contractor_cell_data.contraction, &settings.contractor_contraction);
+                visit_fn("frequency", &mut contractor_cell_data.frequency, &settings.contractor_frequency);
+            }
+            Self::Adhesive => {}
+            Self::FoodSensor (food_sensor_cell_data) => {
+                visit_fn("range", &mut food_sensor_cell_data.range, &settings.food_sensor_range);
+            }
+            Self::LightSensor => {}
+            Self::ContactSensor => {}
+            Self::Spike (spike_cell_data) => {
+                visit_fn("damage", &mut spike_cell_data.damage, &settings.spike_damage);
+            }
+            Self::Armor (armor_cell_data) => {
+                visit_fn("damage_reduction", &mut armor_cell_data.damage_reduction, &settings.armor_damage_reduction);
+            }
+        }
+    }
+
+    // copies the per-type parameters of a genome's cell, but not its stored values
+    pub fn new_from_template (template: &RawCell) -> Self {
+        match template {
+            Self::Fat (fat_cell_data) => Self::Fat (FatCellData {
+                extra_energy: 0.0,
+                extra_material: 0.0,
+                ..fat_cell_data.clone()
+            }),
+            Self::Photosynthesiser => Self::Photosynthesiser,
+            Self::Egg (_) => Self::new_egg_cell(),
+            Self::Mouth => Self::Mouth,
+            Self::Flagellum (flagellum_cell_data) => Self::Flagellum (FlagellumCellData {
+                phase: 0.0,
+                ..flagellum_cell_data.clone()
+            }),
+            Self::Contractor (contractor_cell_data) => Self::Contractor (ContractorCellData {
+                phase: 0.0,
+                ..contractor_cell_data.clone()
+            }),
+            Self::Adhesive => Self::Adhesive,
+            Self::FoodSensor (food_sensor_cell_data) => Self::FoodSensor (FoodSensorCellData {
+                direction: (0.0, 0.0),
+                ..food_sensor_cell_data.clone()
+            }),
+            Self::LightSensor => Self::LightSensor,
+            Self::ContactSensor => Self::ContactSensor,
+            Self::Spike (spike_cell_data) => Self::Spike (spike_cell_data.clone()),
+            Self::Armor (armor_cell_data) => Self::Armor (armor_cell_data.clone()),
+        }
+    }
+
+    // signals emitted by the cell type itself (per second, before multiplying by sensor_signal_emission_rate), on top of what the cell's controller emits
+    // food & light sensors emit their value on channel 0, contact sensors emit theirs on channel 1
+    pub fn get_signal_emission (&self, sensor_value: f64) -> SignalValues {
+        let mut output = [0.; SIGNAL_CHANNEL_COUNT];
+        match self {
+            Self::FoodSensor (_) | Self::LightSensor => output[0] = sensor_value,
+            Self::ContactSensor => output[1] = sensor_value,
+            _ => {}
+        }
+        output
+    }
+
+    // how much shorter this cell currently makes its connections (0 = not at all, 0.5 = half length)
+    pub fn get_contraction (&self) -> f64 {
+        match self {
+            Self::Contractor (contractor_cell_data) => contractor_cell_data.get_contraction(),
+            _ => 0.,
+        }
+    }
+
 }
 
 
@@ -127,3 +398,66 @@ pub struct FatCellData {
     pub material_store_rate: f64,
     pub material_release_rate: f64,
 }
+
+
+
+// hatches into a new organism (built from the mutated genome) once it has stored enough to pay for it
+#[derive(Debug, Clone)]
+pub struct EggCellData {
+    pub stored_energy: f64,
+    pub stored_material: f64,
+}
+
+
+
+// pushes towards the cells it's connected to, the thrust oscillates between 0 and `strength` `frequency` times per second
+#[derive(Debug, Clone)]
+pub struct FlagellumCellData {
+    pub phase: f64, // 0 to 1
+    pub strength: f64,
+    pub frequency: f64,
+}
+
+
+
+// the most a contractor can shorten its connections by, anything close to 1 would give them a rest length of (almost) 0
+pub const MAX_CONTRACTION: f64 = 0.9;
+
+// shortens all of its connections by up to `contraction` (as a fraction of their length), `frequency` times per second
+#[derive(Debug, Clone)]
+pub struct ContractorCellData {
+    pub phase: f64, // 0 to 1
+    pub contraction: f64,
+    pub frequency: f64,
+}
+
+impl ContractorCellData {
+    pub fn get_contraction (&self) -> f64 {
+        self.contraction.clamp(0., MAX_CONTRACTION) * (0.5 - 0.5 * (self.phase * std::f64::consts::TAU).cos())
+    }
+}
+
+
+
+// senses the closest food within `range`, its sensor value goes from 1 (touching) to 0 (at `range` or no food)
+#[derive(Debug, Clone)]
+pub struct FoodSensorCellData {
+    pub direction: (f64, f64), // normalized, (0, 0) when no food is in range
+    pub range: f64,
+}
+
+
+
+// damages every touching cell from another organism by `damage` health per second (scaled by the cell's activity)
+#[derive(Debug, Clone)]
+pub struct SpikeCellData {
+    pub damage: f64,
+}
+
+
+
+// takes less damage from spikes, but has more drag and costs energy & material to keep up (both scale with `damage_reduction`)
+#[derive(Debug, Clone)]
+pub struct ArmorCellData {
+    pub damage_reduction: f64, // 0 = normal damage, 1 = no damage
+}
@@ -0,0 +1,55 @@
+use crate::prelude::*;
+
+
+
+//---------------------------------------------------------------------------------------------------//
+// WARNING: Cell.connections needs to stay synced with ConnectionContainer, use World.connect_cells() //
+// and World.disconnect_cells() instead of changing either one directly                               //
+//---------------------------------------------------------------------------------------------------//
+
+pub type ConnectionID = ArenaID;
+
+// every connection is stored once here, and both of its cells list its id
+pub type ConnectionContainer = IdArena<Connection>;
+
+
+
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub cell_ids: (EntityID, EntityID),
+    pub rest_length_scale: f64, // multiplies cell_connection_distance
+    pub stiffness_scale: f64, // multiplies cell_connection_force
+    pub damping_scale: f64, // multiplies cell_connection_drag
+    pub permeability: f64, // multiplies the energy and material transfer rates
+    pub health: f64,
+}
+
+impl Connection {
+
+    pub fn new (cell_id_1: EntityID, cell_id_2: EntityID) -> Self {
+        Self {
+            cell_ids: (cell_id_1, cell_id_2),
+            rest_length_scale: 1.0,
+            stiffness_scale: 1.0,
+            damping_scale: 1.0,
+            permeability: 1.0,
+            health: 1.0,
+        }
+    }
+
+    // organisms get their connections' properties from their genome
+    pub fn from_genome (cell_id_1: EntityID, cell_id_2: EntityID, genome_connection: &GenomeConnection) -> Self {
+        Self {
+            rest_length_scale: genome_connection.rest_length_scale,
+            stiffness_scale: genome_connection.stiffness_scale,
+            damping_scale: genome_connection.damping_scale,
+            permeability: genome_connection.permeability,
+            ..Self::new(cell_id_1, cell_id_2)
+        }
+    }
+
+    pub fn get_other_cell_id (&self, cell_id: EntityID) -> EntityID {
+        if self.cell_ids.0 == cell_id {self.cell_ids.1} else {self.cell_ids.0}
+    }
+
+}
@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+
+
+// Each genome cell has its own controller, a single layer network that turns the cell's state into behaviour
+// Every output is `bias + sum(weight * input)`, clamped to 0..1, and is computed once per frame by update::update_controller()
+// The default controller has no weights, a bias of 1 for activity & reproduce and a bias of 0 for emitting signals,
+// which makes every cell act the same as it would without a controller
+
+pub const CONTROLLER_INPUT_COUNT: usize = 6 + SIGNAL_CHANNEL_COUNT;
+pub const CONTROLLER_OUTPUT_COUNT: usize = 2 + SIGNAL_CHANNEL_COUNT;
+
+// indices into the inputs
+pub const CONTROLLER_INPUT_ENERGY: usize = 0;
+pub const CONTROLLER_INPUT_MATERIAL: usize = 1;
+pub const CONTROLLER_INPUT_HEALTH: usize = 2;
+pub const CONTROLLER_INPUT_CONNECTED_ACTIVITY: usize = 3; // average activity output of the connected cells
+pub const CONTROLLER_INPUT_SENSOR: usize = 4; // the cell's own sensor value
+pub const CONTROLLER_INPUT_CONNECTED_SENSOR: usize = 5; // average sensor value of the connected cells
+pub const CONTROLLER_INPUT_SIGNALS: usize = 6; // the first of SIGNAL_CHANNEL_COUNT inputs, one per signal channel
+
+// indices into the outputs
+pub const CONTROLLER_OUTPUT_ACTIVITY: usize = 0; // scales flagellum thrust & contractor contraction
+pub const CONTROLLER_OUTPUT_REPRODUCE: usize = 1; // root cells only reproduce while this is at least 0.5
+pub const CONTROLLER_OUTPUT_EMIT_SIGNALS: usize = 2; // the first of SIGNAL_CHANNEL_COUNT outputs, one per signal channel
+
+pub type ControllerInputs = [f64; CONTROLLER_INPUT_COUNT];
+pub type ControllerOutputs = [f64; CONTROLLER_OUTPUT_COUNT];
+
+
+
+#[derive(Debug, Clone)]
+pub struct Controller {
+    pub biases: [f64; CONTROLLER_OUTPUT_COUNT],
+    pub weights: [[f64; CONTROLLER_INPUT_COUNT]; CONTROLLER_OUTPUT_COUNT], // weights[output][input]
+}
+
+impl Controller {
+
+    pub fn new() -> Self {
+        let mut biases = [0.; CONTROLLER_OUTPUT_COUNT];
+        biases[CONTROLLER_OUTPUT_ACTIVITY] = 1.;
+        biases[CONTROLLER_OUTPUT_REPRODUCE] = 1.;
+        Self {
+            biases,
+            weights: [[0.; CONTROLLER_INPUT_COUNT]; CONTROLLER_OUTPUT_COUNT],
+        }
+    }
+
+    // the outputs of the default controller (with no weights, the outputs are just the biases)
+    pub fn get_default_outputs() -> ControllerOutputs {
+        Self::new().biases
+    }
+
+    pub fn evaluate (&self, inputs: &ControllerInputs) -> ControllerOutputs {
+        let mut outputs = [0.; CONTROLLER_OUTPUT_COUNT];
+        for (output_index, output) in outputs.iter_mut().enumerate() {
+            let weighted_sum = self.weights[output_index].iter().zip(inputs).map(|(weight, input)| weight * input).sum::<f64>();
+            *output = (self.biases[output_index] + weighted_sum).clamp(0., 1.);
+        }
+        outputs
+    }
+
+    // calls `visit_fn` with every bias and weight (used for mutations)
+    pub fn visit_values (&mut self, mut visit_fn: impl FnMut(&mut f64)) {
+        for (bias, weights) in self.biases.iter_mut().zip(&mut self.weights) {
+            visit_fn(bias);
+            weights.iter_mut().for_each(&mut visit_fn);
+        }
+    }
+
+}
+
+impl Default for Controller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -13,6 +13,7 @@ pub struct EntityContainer<T: Entity> {
     pub current_index: usize,
     pub empty_slots: u32,
     pub entities_by_pos: Vec<Vec<EntityID>>, // 1d array for grid, 1d array for entities in that slot
+    pub size: WorldSize,
 }
 
 
@@ -44,9 +45,7 @@ impl RawEntity {
     }
 }
 
-pub trait Entity {
-    fn get_texture<'a> (&self, textures: &'a ProgramTextures<'a>) -> &'a Texture<'a>;
-}
+pub trait Entity {}
 
 pub trait Transform {
     type Target;
@@ -59,13 +58,14 @@ pub trait Transform {
 
 impl<T: Entity> EntityContainer<T> {
 
-    pub fn new() -> Self {
-        let grid = vec![vec!(); GRID_WIDTH * GRID_HEIGHT];
+    pub fn new (size: WorldSize) -> Self {
+        let grid = vec![vec!(); size.width * size.height];
         Self {
             master_list: vec!(),
             current_index: 0,
             empty_slots: 0,
             entities_by_pos: grid,
+            size,
         }
     }
 
@@ -86,6 +86,17 @@ impl<T: Entity> EntityContainer<T> {
         entity_data.0.is_some() && entity_data.1 == id.1
     }
 
+    // the number of live entities (master_list also has empty slots)
+    pub fn get_entity_count (&self) -> usize {
+        self.master_list.len() - self.empty_slots as usize
+    }
+
+}
+
+impl<T: Entity> Default for EntityContainer<T> {
+    fn default() -> Self {
+        Self::new(WorldSize::default())
+    }
 }
 
 
@@ -99,13 +110,13 @@ impl<T: Entity + AsRef<RawEntity> + AsMut<RawEntity>> EntityContainer<T> {
     pub fn add_entity (&mut self, entity: T) -> Option<EntityID> {
         let raw_entity = entity.as_ref();
 
-        if self.master_list.len() >= MAX_ENTITIES_COUNT {return None;}
+        if self.get_entity_count() >= self.size.max_entities_count {return None;}
         let (current_grid_x, current_grid_y) = (raw_entity.current_grid_x, raw_entity.current_grid_y);
 
-        // add to master list
+        // add to master list (empty slots are always re-used once the list has reached max_entities_count)
         let entity_id;
         let master_list_len = self.master_list.len();
-        if self.empty_slots as f64 / master_list_len as f64 >= 0.05 {
+        if self.empty_slots > 0 && (self.empty_slots as f64 / master_list_len as f64 >= 0.05 || master_list_len >= self.size.max_entities_count) {
             // re-use slot
             while self.master_list[self.current_index].0.is_some() {
                 self.current_index = (self.current_index + 1) % master_list_len;
@@ -121,7 +132,7 @@ impl<T: Entity + AsRef<RawEntity> + AsMut<RawEntity>> EntityContainer<T> {
         }
 
         // add to entities_by_pos
-        self.entities_by_pos[current_grid_x + current_grid_y * GRID_WIDTH].push(entity_id);
+        self.entities_by_pos[current_grid_x + current_grid_y * self.size.width].push(entity_id);
 
         Some(entity_id)
     }
@@ -140,7 +151,7 @@ impl<T: Entity + AsRef<RawEntity> + AsMut<RawEntity>> EntityContainer<T> {
             // remove if should_be_removed
             if raw_entity.should_be_removed {
                 let (grid_x, grid_y) = (raw_entity.current_grid_x, raw_entity.current_grid_y);
-                let slot = &mut self.entities_by_pos[grid_x + grid_y * GRID_WIDTH];
+                let slot = &mut self.entities_by_pos[grid_x + grid_y * self.size.width];
                 let slot_pos = fns::find_item_index(slot, &id).unwrap();
                 slot.remove(slot_pos);
                 indicies_to_erase.push(id.0);
@@ -153,10 +164,10 @@ impl<T: Entity + AsRef<RawEntity> + AsMut<RawEntity>> EntityContainer<T> {
             if old_x == new_x && old_y == new_y {continue;}
             raw_entity.current_grid_x = new_x;
             raw_entity.current_grid_y = new_y;
-            let old_slot = &mut self.entities_by_pos[old_x + old_y * GRID_WIDTH];
+            let old_slot = &mut self.entities_by_pos[old_x + old_y * self.size.width];
             let entity_index = fns::find_item_index(old_slot, &id).unwrap_or_else(|| panic!("An entity was listed as being in slot ({old_x}, {old_y}), but that entity's ID was not in that slot"));
             old_slot.remove(entity_index);
-            let new_slot = &mut self.entities_by_pos[new_x + new_y * GRID_WIDTH];
+            let new_slot = &mut self.entities_by_pos[new_x + new_y * self.size.width];
             new_slot.push(id);
 
         }
@@ -1,20 +1,29 @@
 use crate::prelude::*;
 use std::io::Error as IoError;
+#[cfg(feature = "sdl")]
 use sdl2::render::{TextureValueError, UpdateTextureError};
-use ab_glyph::InvalidFont;
+#[cfg(feature = "sdl")]
+use ab_glyph::{Glyph, InvalidFont};
 
 
 
 #[derive(Debug)]
 pub enum ProgramError {
 
+    #[cfg(feature = "sdl")]
     GlyphRenderError {
         glyph: Glyph,
     },
 
+    InvalidSnapshot (String),
+    InvalidSettings (String),
+
     String (String),
+    #[cfg(feature = "sdl")]
     TextureValueError (TextureValueError),
+    #[cfg(feature = "sdl")]
     UpdateTextureError (UpdateTextureError),
+    #[cfg(feature = "sdl")]
     InvalidFont (InvalidFont),
     IoError (IoError),
 
@@ -28,18 +37,21 @@ impl From<String> for ProgramError {
     }
 }
 
+#[cfg(feature = "sdl")]
 impl From<TextureValueError> for ProgramError {
     fn from(input: TextureValueError) -> Self {
         Self::TextureValueError(input)
     }
 }
 
+#[cfg(feature = "sdl")]
 impl From<UpdateTextureError> for ProgramError {
     fn from(input: UpdateTextureError) -> Self {
         Self::UpdateTextureError(input)
     }
 }
 
+#[cfg(feature = "sdl")]
 impl From<InvalidFont> for ProgramError {
     fn from(input: InvalidFont) -> Self {
         Self::InvalidFont(input)
@@ -1,154 +1,282 @@
 use crate::prelude::*;
-use sdl2::{keyboard::Keycode, render::TextureCreator, video::WindowContext, event::Event, mouse::MouseState, EventPump};
-use ab_glyph::FontVec;
 
 
 
-pub struct ProgramData<'a> {
+pub struct World {
 
-    pub start_instant: Instant,
     pub frame_count: u64,
-    pub exit: bool,
-
-    pub camera: Camera,
-    pub selected_entity: EntitySelection,
-    pub keys_pressed: HashMap<Keycode, ()>,
-
-    pub render_data: RenderData<'a>,
+    pub seed: u64,
+    pub size: WorldSize,
+    pub settings: SimSettings,
+    pub profile_data: Option<ProfileData>,
 
     pub cells: EntityContainer<Cell>,
     pub food: EntityContainer<Food>,
+    pub connections: ConnectionContainer,
+    pub organisms: OrganismContainer,
 
 }
 
-impl<'a> ProgramData<'a> {
+impl World {
 
-    pub fn new (render_data: RenderData<'a>) -> Self {
+    pub fn new (size: WorldSize, settings: SimSettings, seed: u64) -> Self {
         Self {
-
-            start_instant: Instant::now(),
             frame_count: 0,
-            exit: false,
-
-            camera: Camera {
-                x: 0.,
-                y: 0.,
-                zoom: 0.2,
-            },
-            selected_entity: EntitySelection::None,
-            keys_pressed: HashMap::new(),
+            seed,
+            size,
+            settings,
+            profile_data: None,
+            cells: EntityContainer::new(size),
+            food: EntityContainer::new(size),
+            connections: ConnectionContainer::new(),
+            organisms: OrganismContainer::new(),
+        }
+    }
 
-            render_data,
+    pub fn step (&mut self, dt: f64) {
+        update::update_cells(self, dt);
+        self.frame_count += 1;
+    }
 
-            cells: EntityContainer::new(),
-            food: EntityContainer::new(),
+    // adds the connection to the connection list and to both of its cells
+    pub fn connect_cells (&mut self, connection: Connection) -> ConnectionID {
+        let cell_ids = connection.cell_ids;
+        let connection_id = self.connections.add(connection);
+        self.cells.get_mut(cell_ids.0).unwrap().connections.push(connection_id);
+        self.cells.get_mut(cell_ids.1).unwrap().connections.push(connection_id);
+        connection_id
+    }
 
+    // removes the connection from the connection list and from whichever of its cells still exist
+    pub fn disconnect_cells (&mut self, connection_id: ConnectionID) {
+        let Some(connection) = self.connections.remove(connection_id) else {return;};
+        for cell_id in [connection.cell_ids.0, connection.cell_ids.1] {
+            let Some(cell) = self.cells.get_mut(cell_id) else {continue;};
+            cell.connections.retain(|&id| id != connection_id);
         }
     }
 
-    pub fn key_is_pressed (&self, keycode: Keycode) -> bool {
-        self.keys_pressed.contains_key(&keycode)
+    // adds the time since `phase_instant` to the profile data (if profiling) and resets `phase_instant`
+    pub fn record_phase_time (&mut self, phase: ProfilePhase, phase_instant: &mut Instant) {
+        if let Some(profile_data) = &mut self.profile_data {
+            profile_data.add_phase_time(phase, phase_instant.elapsed().as_secs_f64());
+        }
+        *phase_instant = Instant::now();
     }
 
-}
+    pub fn get_rng_for_entity (&self, entity_index: usize) -> SimRng {
+        SimRng::new_for_entity(self.seed, self.frame_count, entity_index)
+    }
 
+    pub fn get_stats (&self) -> WorldStats {
+        let mut stats = WorldStats::new(self.frame_count);
+        for cell in self.cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()) {
+            stats.cell_count += 1;
+            if cell.is_active {stats.active_cell_count += 1;}
+            stats.cells_energy += cell.get_total_energy();
+            stats.cells_material += cell.get_total_material();
+        }
+        for organism in self.organisms.iter() {
+            stats.organism_count += 1;
+            stats.largest_organism_cell_count = stats.largest_organism_cell_count.max(organism.cell_ids.len());
+        }
+        stats.organism_split_count = self.organisms.split_count;
+        stats.organism_merge_count = self.organisms.merge_count;
+        for food in self.food.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()) {
+            stats.food_count += 1;
+            stats.food_energy += food.energy;
+            stats.food_material += food.material;
+        }
+        stats
+    }
 
+    pub fn add_test_data (&mut self) {
+
+        let genome = Arc::new(Genome::new_test_triad(&self.settings));
+        let start_vels = [(5.0, 0.0), (-5.0, 5.0), (0.0, -5.0)];
+        // every cell gets enough energy and material for its organism's root cell to pay for one offspring, on top of the usual 1.0 energy
+        let energy = 1.0 + genome.get_energy_cost(&self.settings) + self.settings.reproduction_energy_reserve;
+        let material = genome.get_material_cost(&self.settings);
+        let (triad_count_x, triad_count_y) = ((self.size.width / 3).min(30), (self.size.height / 3).min(30));
+        'add_triads: for x in 0..triad_count_x {
+            for y in 0..triad_count_y {
+
+                let origin = (x as f64 * 3. + 1.5, y as f64 * 3. + 1.5);
+                // stop early if max_entities_count is too low to fit every triad
+                let Some(cell_ids) = Genome::build_organism(&genome, origin, energy, material, self) else {break 'add_triads;};
+                for (cell_id, vel) in cell_ids.into_iter().zip(start_vels) {
+                    let cell = self.cells.get_mut(cell_id).unwrap();
+                    (cell.x_vel, cell.y_vel) = vel;
+                }
+
+            }
+        }
 
-pub type GlyphCache<'a> = HashMap<HashableGlyph, GlyphTexture<'a>>;
+        self.food.add_entity(Food::new(3.5, 2.5, 1.0, 1.0));
+        update::update_organisms(self, 0.);
 
+    }
 
+}
 
 
 
-pub struct RenderData<'a> {
-    pub textures: ProgramTextures<'a>,
-    pub texture_creator: &'a TextureCreator<WindowContext>,
-    pub font: FontVec,
-    pub glyph_cache: GlyphCache<'a>,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldSize {
+    pub width: usize,
+    pub height: usize,
+    pub max_entities_count: usize,
+    pub boundary_mode: BoundaryMode,
 }
 
-impl<'a> RenderData<'a> {
-    pub fn new (textures: ProgramTextures<'a>, font: FontVec, texture_creator: &'a TextureCreator<WindowContext>) -> Self {
+impl WorldSize {
+
+    // sizes below MIN_GRID_SIZE are raised to it, since some of the update code assumes the world is at least that big
+    pub fn new (width: usize, height: usize) -> Self {
+        let (width, height) = (width.max(MIN_GRID_SIZE), height.max(MIN_GRID_SIZE));
         Self {
-            textures,
-            texture_creator,
-            font,
-            glyph_cache: HashMap::new(),
+            width,
+            height,
+            max_entities_count: width * height / 2,
+            boundary_mode: BoundaryMode::Walls,
         }
     }
-}
-
 
+    pub fn wraps (&self) -> bool {
+        self.boundary_mode == BoundaryMode::Wrap
+    }
 
-#[derive(PartialEq)]
-pub enum EntitySelection {
-    None,
-    Cell (EntityID),
-    Food (EntityID),
-}
+    // shortest vector from one position to another, taking wrapped edges into account
+    pub fn get_pos_change (&self, from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+        let (mut dx, mut dy) = (to.0 - from.0, to.1 - from.1);
+        if self.wraps() {
+            dx = fns::wrap_delta(dx, self.width as f64);
+            dy = fns::wrap_delta(dy, self.height as f64);
+        }
+        (dx, dy)
+    }
 
+    pub fn wrap_pos (&self, pos: (f64, f64)) -> (f64, f64) {
+        (fns::wrap_coord(pos.0, self.width as f64), fns::wrap_coord(pos.1, self.height as f64))
+    }
 
+    // wraps the position if the world wraps, otherwise clamps it inside the walls
+    pub fn constrain_pos (&self, pos: (f64, f64)) -> (f64, f64) {
+        if self.wraps() {
+            self.wrap_pos(pos)
+        } else {
+            (pos.0.clamp(0., self.width as f64 - 0.000001), pos.1.clamp(0., self.height as f64 - 0.000001))
+        }
+    }
 
-#[derive(Hash, Eq, PartialEq)]
-pub struct HashableGlyph {
-    pub glyph_id: GlyphId,
-    pub scale_x: u32,
-    pub scale_y: u32,
 }
 
-impl HashableGlyph {
-    pub fn from_glyph (glyph: &Glyph) -> Self {
-        Self {
-            glyph_id: glyph.id,
-            scale_x: glyph.scale.x as u32,
-            scale_y: glyph.scale.y as u32,
-        }
+impl Default for WorldSize {
+    fn default() -> Self {
+        Self::new(DEFAULT_GRID_WIDTH, DEFAULT_GRID_HEIGHT)
     }
 }
 
-
-
-pub struct GlyphTexture<'a> {
-    pub texture: Texture<'a>,
-    pub origin_x: i32,
-    pub origin_y: i32,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryMode {
+    Walls,
+    Wrap,
 }
 
 
 
+// parses "<width>x<height>", eg "128x128"
+impl std::str::FromStr for WorldSize {
+    type Err = String;
+    fn from_str (input: &str) -> Result<Self, Self::Err> {
+        let Some((width, height)) = input.split_once('x') else {
+            return Err(format!("expected \"<width>x<height>\", got \"{input}\""));
+        };
+        let width: usize = width.trim().parse().map_err(|_| format!("\"{width}\" is not a valid width"))?;
+        let height: usize = height.trim().parse().map_err(|_| format!("\"{height}\" is not a valid height"))?;
+        if width < MIN_GRID_SIZE || height < MIN_GRID_SIZE {
+            return Err(format!("the world has to be at least {MIN_GRID_SIZE}x{MIN_GRID_SIZE}"));
+        }
+        Ok(Self::new(width, height))
+    }
+}
 
 
-#[derive(Debug)]
-pub struct EventsData {
-    pub list: Vec<Event>,
-    pub mouse_state: MouseState,
-}
 
-impl EventsData {
+// accumulates real time and hands out a whole number of fixed-size steps, so a run doesn't depend on the framerate
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    pub dt: f64,
+    pub time_accumulated: f64,
+}
 
-    pub fn from_event_pump (event_pump: &mut EventPump) -> Self {
+impl FixedTimestep {
+    // dt has to be greater than 0 (see fns::parse_dt_arg_value()), otherwise get_step_count() never finishes catching up
+    pub fn new (dt: f64) -> Self {
         Self {
-            list: event_pump.poll_iter().filter(Self::filter_event).collect(),
-            mouse_state: event_pump.mouse_state(),
+            dt,
+            time_accumulated: 0.,
         }
     }
-
-    pub fn filter_event (event: &Event) -> bool {
-        matches!(event,
-            Event::Quit {..} |
-            Event::KeyDown {..} |
-            Event::KeyUp {..} |
-            Event::MouseWheel {..} |
-            Event::MouseButtonDown {..}
-        )
+    pub fn get_step_count (&mut self, elapsed: f64, time_scale: f64) -> u32 {
+        self.time_accumulated += elapsed.min(FIXED_TIMESTEP_MAX_CATCHUP) * time_scale;
+        let step_count = (self.time_accumulated / self.dt).floor();
+        self.time_accumulated -= step_count * self.dt;
+        step_count as u32
     }
-
 }
 
 
 
+#[derive(Debug, Clone)]
+pub struct WorldStats {
+    pub frame_count: u64,
+    pub cell_count: usize,
+    pub active_cell_count: usize,
+    pub organism_count: usize,
+    pub largest_organism_cell_count: usize,
+    pub organism_split_count: u64,
+    pub organism_merge_count: u64,
+    pub cells_energy: f64,
+    pub cells_material: f64,
+    pub food_count: usize,
+    pub food_energy: f64,
+    pub food_material: f64,
+}
 
+impl WorldStats {
+    pub fn new (frame_count: u64) -> Self {
+        Self {
+            frame_count,
+            cell_count: 0,
+            active_cell_count: 0,
+            organism_count: 0,
+            largest_organism_cell_count: 0,
+            organism_split_count: 0,
+            organism_merge_count: 0,
+            cells_energy: 0.,
+            cells_material: 0.,
+            food_count: 0,
+            food_energy: 0.,
+            food_material: 0.,
+        }
+    }
+}
 
+impl fmt::Display for WorldStats {
+    fn fmt (&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Frames: {}", self.frame_count)?;
+        writeln!(f, "Cells: {} ({} active)", self.cell_count, self.active_cell_count)?;
+        writeln!(f, "Organisms: {} (largest: {} cells, {} splits, {} merges)", self.organism_count, self.largest_organism_cell_count, self.organism_split_count, self.organism_merge_count)?;
+        writeln!(f, "Cell energy: {}", self.cells_energy)?;
+        writeln!(f, "Cell material: {}", self.cells_material)?;
+        writeln!(f, "Food: {}", self.food_count)?;
+        writeln!(f, "Food energy: {}", self.food_energy)?;
+        writeln!(f, "Food material: {}", self.food_material)?;
+        writeln!(f, "Total energy: {}", self.cells_energy + self.food_energy)?;
+        write!(f, "Total material: {}", self.cells_material + self.food_material)
+    }
+}
 
 
 
@@ -163,23 +291,28 @@ pub struct Food {
 
 impl Food {
     pub fn new (x: f64, y: f64, energy: f64, material: f64) -> Self {
-        let size = material / 4. + 0.25;
+        let size = Self::get_size(material);
         Self {
             energy,
             material,
             entity: RawEntity::new(x, y, size, size),
         }
     }
+    pub fn get_size (material: f64) -> f64 {
+        material / 4. + 0.25
+    }
+    pub fn update_size (&mut self) {
+        let size = Self::get_size(self.material);
+        self.entity.width = size;
+        self.entity.height = size;
+    }
+    // includes whatever the cell had stored (in fat & egg cells)
     pub fn from_cell (cell: &Cell) -> Self {
-        Self::new(cell.entity.x, cell.entity.y, cell.energy, cell.material)
+        Self::new(cell.entity.x, cell.entity.y, cell.get_total_energy(), cell.get_total_material())
     }
 }
 
-impl Entity for Food {
-    fn get_texture<'a> (&self, textures: &'a ProgramTextures<'a>) -> &'a Texture<'a> {
-        &textures.food
-    }
-}
+impl Entity for Food {}
 
 impl AsRef<RawEntity> for Food {
     fn as_ref(&self) -> &RawEntity {
@@ -203,82 +336,18 @@ pub struct Camera {
     pub zoom: f64, // >1 means zoomed in, <1 means zoomed out
 }
 
-
-
-pub struct ProgramTextures<'a> {
-    pub ground: Texture<'a>,
-    pub black_ground: Texture<'a>,
-    pub food: Texture<'a>,
-    pub circle: Texture<'a>,
-}
-
-
-
-
-
-#[derive(Debug)]
-pub struct Area {
-    pub screen_size: (u32, u32),
-    pub x: f64,
-    pub y: f64,
-    pub width: f64,
-    pub height: f64,
-}
-
-impl Area {
-
-    pub fn new (screen_size: (u32, u32)) -> Self {
+impl Camera {
+    pub fn new() -> Self {
         Self {
-            screen_size,
             x: 0.,
             y: 0.,
-            width: 1.,
-            height: 1.,
+            zoom: 0.2,
         }
     }
+}
 
-    pub fn get_basic_sub_area (&self, x: f64, y: f64, width: f64, height: f64) -> Self {
-        Self {
-            screen_size: self.screen_size,
-            x: self.x + x * self.width,
-            y: self.y + y * self.height,
-            width:  width  * self.width,
-            height: height * self.height,
-        }
-    }
-
-    pub fn get_sub_area (&self, x: f64, y: f64, width: f64, height: f64, natural_x: f64, natural_width: f64) -> Self {
-        let aspect_ratio = (self.screen_size.0 as f64 / self.screen_size.1 as f64) * (self.width / self.height);
-        Self {
-            screen_size: self.screen_size,
-            x: self.x + x * self.width + natural_x * self.width / aspect_ratio,
-            y: self.y + y * self.height,
-            width:  width  * self.width + natural_width * self.width / aspect_ratio,
-            height: height * self.height,
-        }
-    }
-
-    pub fn get_point (&self, x: f64, y: f64, natural_x: f64) -> (i32, i32) {
-        let aspect_ratio = (self.screen_size.0 as f64 / self.screen_size.1 as f64) * (self.width / self.height);
-        let mut point_x = self.x + x * self.width + natural_x * self.width / aspect_ratio;
-        let mut point_y = self.y + y * self.height;
-        point_x *= self.screen_size.0 as f64;
-        point_y *= self.screen_size.1 as f64;
-        (point_x.round() as i32, point_y.round() as i32)
-    }
-
-    pub fn to_rect (&self) -> Rect {
-        let x = self.x * self.screen_size.0 as f64;
-        let y = self.y * self.screen_size.1 as f64;
-        let width  = self.width  * self.screen_size.0 as f64;
-        let height = self.height * self.screen_size.1 as f64;
-        let end_x = x + width;
-        let end_y = y + height;
-        let final_x = x.round() as i32;
-        let final_y = y.round() as i32;
-        let final_width = (end_x.round() as i32) - final_x;
-        let final_height = (end_y.round() as i32) - final_y;
-        Rect::new(final_x, final_y, final_width as u32, final_height as u32)
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
     }
-
 }
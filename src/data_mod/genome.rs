@@ -0,0 +1,285 @@
+use crate::prelude::*;
+
+
+
+// An organism's body plan. Every cell of an organism shares the same genome (through an Arc), and offspring are built from it
+// The cells' RawCell values hold the per-type parameters (thresholds, rates, etc), the stored values (like FatCellData.extra_energy) are ignored
+
+#[derive(Debug, Clone)]
+pub struct Genome {
+    pub cells: Vec<GenomeCell>,
+    pub connections: Vec<GenomeConnection>,
+    pub generation: u64,
+    pub mutation_history: Vec<MutationRecord>, // oldest first, only the last `mutation_history_max_len` are kept
+}
+
+#[derive(Debug, Clone)]
+pub struct GenomeCell {
+    pub raw_cell: RawCell,
+    pub offset: (f64, f64), // position relative to the organism's origin
+    pub controller: Controller,
+}
+
+// copied into the organism's Connection when it's built
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenomeConnection {
+    pub cell_indices: (usize, usize), // indices into `cells`
+    pub rest_length_scale: f64,
+    pub stiffness_scale: f64,
+    pub damping_scale: f64,
+    pub permeability: f64,
+}
+
+
+
+impl Genome {
+
+    pub fn new (cells: Vec<GenomeCell>, connections: Vec<GenomeConnection>) -> Self {
+        Self {
+            cells,
+            connections,
+            generation: 0,
+            mutation_history: vec!(),
+        }
+    }
+
+    // the body plan used by World::add_test_data()
+    pub fn new_test_triad (settings: &SimSettings) -> Self {
+        Self::new(
+            vec!(
+                GenomeCell::new(RawCell::new_fat_cell(settings), (0.0, 0.0)),
+                GenomeCell::new(RawCell::new_fat_cell(settings), (1.0, 0.2)),
+                GenomeCell::new(RawCell::new_fat_cell(settings), (0.2, 1.0)),
+            ),
+            vec!(GenomeConnection::new(0, 1), GenomeConnection::new(0, 2), GenomeConnection::new(1, 2)),
+        )
+    }
+
+    pub fn get_energy_cost (&self, settings: &SimSettings) -> f64 {
+        self.cells.len() as f64 * settings.reproduction_cell_energy
+    }
+
+    pub fn get_material_cost (&self, settings: &SimSettings) -> f64 {
+        self.cells.len() as f64 * settings.reproduction_cell_material
+    }
+
+    // distance from the origin to the furthest cell edge
+    pub fn get_radius (&self) -> f64 {
+        self.cells.iter()
+            .map(|genome_cell| fns::vec_len(genome_cell.offset) + 0.5)
+            .fold(0.5, f64::max)
+    }
+
+    // each kind of mutation is rolled separately, and every change is added to the mutation history
+    pub fn mutate (&mut self, settings: &SimSettings, rng: &mut SimRng) {
+        self.generation += 1;
+        let mut mutations = vec!();
+
+        // cell types, parameters & controllers
+        for (cell_index, genome_cell) in self.cells.iter_mut().enumerate() {
+            if rng.chance(settings.mutation_cell_type_chance) {
+                let old_type_index = genome_cell.raw_cell.get_type_index();
+                let new_type_index = (old_type_index + 1 + rng.range_usize(CELL_TYPE_COUNT - 1)) % CELL_TYPE_COUNT;
+                let old_type = genome_cell.raw_cell.get_type_name().to_string();
+                genome_cell.raw_cell = RawCell::new_of_type(new_type_index, settings);
+                mutations.push(Mutation::ChangeCellType {cell_index, old_type, new_type: genome_cell.raw_cell.get_type_name().to_string()});
+            }
+            genome_cell.raw_cell.visit_parameters(settings, |parameter, value, range| {
+                if !rng.chance(settings.mutation_parameter_chance) {return;}
+                let old_value = *value;
+                *value = range.clamp(old_value + rng.range_f64(-1., 1.) * settings.mutation_parameter_amount * (range.max - range.min));
+                mutations.push(Mutation::ChangeParameter {cell_index, parameter: parameter.to_string(), old_value, new_value: *value});
+            });
+            // a controller has a lot of values, so all of a cell's controller changes are recorded together to keep them from filling the history
+            let (mut changed_count, mut total_change) = (0, 0.);
+            let max_weight = settings.controller_max_weight.abs(); // settings files can't make it negative, but settings made in code can
+            genome_cell.controller.visit_values(|value| {
+                if !rng.chance(settings.mutation_controller_chance) {return;}
+                let old_value = *value;
+                *value = (old_value + rng.range_f64(-1., 1.) * settings.mutation_controller_amount).clamp(-max_weight, max_weight);
+                changed_count += 1;
+                total_change += (*value - old_value).abs();
+            });
+            if changed_count > 0 {
+                mutations.push(Mutation::ChangeController {cell_index, changed_count, total_change});
+            }
+        }
+
+        // connection parameters
+        for connection in &mut self.connections {
+            let cell_indices = connection.cell_indices;
+            connection.visit_parameters(settings, |parameter, value, range| {
+                if !rng.chance(settings.mutation_parameter_chance) {return;}
+                let old_value = *value;
+                *value = range.clamp(old_value + rng.range_f64(-1., 1.) * settings.mutation_parameter_amount * (range.max - range.min));
+                mutations.push(Mutation::ChangeConnectionParameter {cell_indices, parameter: parameter.to_string(), old_value, new_value: *value});
+            });
+        }
+
+        // remove cell (the root cell is never removed)
+        if self.cells.len() > 1 && rng.chance(settings.mutation_remove_cell_chance) {
+            let cell_index = 1 + rng.range_usize(self.cells.len() - 1);
+            self.remove_cell(cell_index);
+            mutations.push(Mutation::RemoveCell {cell_index});
+        }
+
+        // add cell (connected to a random existing cell)
+        if rng.chance(settings.mutation_add_cell_chance) {
+            let connected_index = rng.range_usize(self.cells.len());
+            let angle = rng.range_f64(0., std::f64::consts::TAU);
+            let connected_offset = self.cells[connected_index].offset;
+            let offset = (connected_offset.0 + angle.cos() * settings.cell_connection_distance, connected_offset.1 + angle.sin() * settings.cell_connection_distance);
+            let raw_cell = RawCell::new_of_type(rng.range_usize(CELL_TYPE_COUNT), settings);
+            let cell_index = self.cells.len();
+            mutations.push(Mutation::AddCell {cell_index, cell_type: raw_cell.get_type_name().to_string()});
+            self.cells.push(GenomeCell::new(raw_cell, offset));
+            self.connections.push(GenomeConnection::new(connected_index, cell_index));
+        }
+
+        // remove connection
+        if !self.connections.is_empty() && rng.chance(settings.mutation_remove_connection_chance) {
+            let (cell_index_1, cell_index_2) = self.connections.remove(rng.range_usize(self.connections.len())).cell_indices;
+            mutations.push(Mutation::RemoveConnection (cell_index_1, cell_index_2));
+        }
+
+        // add connection (between two cells that aren't connected yet)
+        if self.cells.len() > 1 && rng.chance(settings.mutation_add_connection_chance) {
+            let cell_index_1 = rng.range_usize(self.cells.len());
+            let mut cell_index_2 = rng.range_usize(self.cells.len() - 1);
+            if cell_index_2 >= cell_index_1 {cell_index_2 += 1;}
+            if !self.has_connection(cell_index_1, cell_index_2) {
+                self.connections.push(GenomeConnection::new(cell_index_1, cell_index_2));
+                mutations.push(Mutation::AddConnection (cell_index_1, cell_index_2));
+            }
+        }
+
+        // record mutations
+        for mutation in mutations {
+            self.mutation_history.push(MutationRecord {generation: self.generation, mutation});
+        }
+        let max_len = settings.mutation_history_max_len as usize;
+        if self.mutation_history.len() > max_len {
+            self.mutation_history.drain(..self.mutation_history.len() - max_len);
+        }
+
+    }
+
+    // removes the cell and its connections, and shifts the indices of the cells after it
+    pub fn remove_cell (&mut self, cell_index: usize) {
+        self.cells.remove(cell_index);
+        self.connections.retain(|connection| connection.cell_indices.0 != cell_index && connection.cell_indices.1 != cell_index);
+        for connection in &mut self.connections {
+            let cell_indices = &mut connection.cell_indices;
+            if cell_indices.0 > cell_index {cell_indices.0 -= 1;}
+            if cell_indices.1 > cell_index {cell_indices.1 -= 1;}
+        }
+    }
+
+    pub fn has_connection (&self, cell_index_1: usize, cell_index_2: usize) -> bool {
+        self.connections.iter().any(|connection| connection.cell_indices == (cell_index_1, cell_index_2) || connection.cell_indices == (cell_index_2, cell_index_1))
+    }
+
+    // returns the ids of the added cells, or None if there wasn't room for the whole organism
+    pub fn build_organism (genome: &Arc<Genome>, origin: (f64, f64), energy: f64, material: f64, world: &mut World) -> Option<Vec<EntityID>> {
+        let cells = &mut world.cells;
+        if cells.get_entity_count() + genome.cells.len() > cells.size.max_entities_count {return None;}
+
+        let mut cell_ids = Vec::with_capacity(genome.cells.len());
+        for (i, genome_cell) in genome.cells.iter().enumerate() {
+            let pos = cells.size.constrain_pos((origin.0 + genome_cell.offset.0, origin.1 + genome_cell.offset.1));
+            let mut cell = Cell::new(RawCell::new_from_template(&genome_cell.raw_cell), pos.0, pos.1, 1.0, energy, material);
+            cell.genome = Some(genome.clone());
+            cell.genome_index = i;
+            cell_ids.push(cells.add_entity(cell)?);
+        }
+
+        for genome_connection in &genome.connections {
+            let (cell_index_1, cell_index_2) = genome_connection.cell_indices;
+            world.connect_cells(Connection::from_genome(cell_ids[cell_index_1], cell_ids[cell_index_2], genome_connection));
+        }
+
+        Some(cell_ids)
+    }
+
+}
+
+
+
+impl GenomeCell {
+    pub fn new (raw_cell: RawCell, offset: (f64, f64)) -> Self {
+        Self {
+            raw_cell,
+            offset,
+            controller: Controller::new(),
+        }
+    }
+}
+
+
+
+impl GenomeConnection {
+    pub fn new (cell_index_1: usize, cell_index_2: usize) -> Self {
+        Self {
+            cell_indices: (cell_index_1, cell_index_2),
+            rest_length_scale: 1.0,
+            stiffness_scale: 1.0,
+            damping_scale: 1.0,
+            permeability: 1.0,
+        }
+    }
+    // every parameter that can mutate, along with the range it has to stay in
+    pub fn visit_parameters (&mut self, settings: &SimSettings, mut visit_fn: impl FnMut(&'static str, &mut f64, &SettingRange)) {
+        visit_fn("rest_length_scale", &mut self.rest_length_scale, &settings.connection_rest_length_scale);
+        visit_fn("stiffness_scale", &mut self.stiffness_scale, &settings.connection_stiffness_scale);
+        visit_fn("damping_scale", &mut self.damping_scale, &settings.connection_damping_scale);
+        visit_fn("permeability", &mut self.permeability, &settings.connection_permeability);
+    }
+}
+
+
+
+#[derive(Debug, Clone)]
+pub struct MutationRecord {
+    pub generation: u64,
+    pub mutation: Mutation,
+}
+
+// cell indices are the indices at the time of the mutation
+#[derive(Debug, Clone)]
+pub enum Mutation {
+    ChangeParameter {cell_index: usize, parameter: String, old_value: f64, new_value: f64},
+    ChangeCellType {cell_index: usize, old_type: String, new_type: String},
+    ChangeConnectionParameter {cell_indices: (usize, usize), parameter: String, old_value: f64, new_value: f64},
+    ChangeController {cell_index: usize, changed_count: usize, total_change: f64}, // total_change is the sum of how much each value changed by
+    AddCell {cell_index: usize, cell_type: String},
+    RemoveCell {cell_index: usize},
+    AddConnection (usize, usize),
+    RemoveConnection (usize, usize),
+}
+
+impl fmt::Display for MutationRecord {
+    fn fmt (&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "gen {}: ", self.generation)?;
+        match &self.mutation {
+            Mutation::ChangeParameter {cell_index, parameter, old_value, new_value} => write!(f, "cell {cell_index} {parameter}: {old_value} -> {new_value}"),
+            Mutation::ChangeCellType {cell_index, old_type, new_type} => write!(f, "cell {cell_index} type: {old_type} -> {new_type}"),
+            Mutation::ChangeConnectionParameter {cell_indices, parameter, old_value, new_value} => write!(f, "connection {}-{} {parameter}: {old_value} -> {new_value}", cell_indices.0, cell_indices.1),
+            Mutation::ChangeController {cell_index, changed_count, total_change} => write!(f, "cell {cell_index} controller: values changed: {changed_count}, total change: {total_change}"),
+            Mutation::AddCell {cell_index, cell_type} => write!(f, "added {cell_type} cell {cell_index}"),
+            Mutation::RemoveCell {cell_index} => write!(f, "removed cell {cell_index}"),
+            Mutation::AddConnection (cell_index_1, cell_index_2) => write!(f, "connected cells {cell_index_1} and {cell_index_2}"),
+            Mutation::RemoveConnection (cell_index_1, cell_index_2) => write!(f, "disconnected cells {cell_index_1} and {cell_index_2}"),
+        }
+    }
+}
+
+
+
+// everything needed to build an offspring once the main update pass is done
+pub struct OffspringData {
+    pub genome: Arc<Genome>,
+    pub origin: (f64, f64),
+    pub energy: f64, // per cell
+    pub material: f64, // per cell
+    pub egg_cell_index: Option<usize>, // set when an egg hatches, see update::collect_hatched_egg_resources()
+}
@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+
+
+//-------------------------------------------------------------------------------------//
+// WARNING: ids are (index, generation), a removed slot gets reused with a generation  //
+// one higher, so old ids to it stop being valid instead of pointing at the new item   //
+//-------------------------------------------------------------------------------------//
+
+pub type ArenaID = (usize, u32);
+
+// a list of items that are referred to by id, like ConnectionContainer and OrganismContainer
+pub struct IdArena<T> {
+    pub list: Vec<(Option<T>, u32)>,
+    pub empty_slots: Vec<usize>,
+}
+
+
+
+impl<T> IdArena<T> {
+
+    pub fn new() -> Self {
+        Self {
+            list: vec!(),
+            empty_slots: vec!(),
+        }
+    }
+
+    pub fn get (&self, id: ArenaID) -> Option<&T> {
+        let item_data = self.list.get(id.0)?;
+        let Some(item) = &item_data.0 else {return None;};
+        fns::some_if(item_data.1 == id.1, || item)
+    }
+
+    pub fn get_mut (&mut self, id: ArenaID) -> Option<&mut T> {
+        let item_data = self.list.get_mut(id.0)?;
+        let Some(item) = &mut item_data.0 else {return None;};
+        fns::some_if(item_data.1 == id.1, || item)
+    }
+
+    pub fn add (&mut self, item: T) -> ArenaID {
+        if let Some(index) = self.empty_slots.pop() {
+            let generation = self.list[index].1 + 1;
+            self.list[index] = (Some(item), generation);
+            (index, generation)
+        } else {
+            self.list.push((Some(item), 0));
+            (self.list.len() - 1, 0)
+        }
+    }
+
+    pub fn remove (&mut self, id: ArenaID) -> Option<T> {
+        self.get(id)?;
+        self.empty_slots.push(id.0);
+        self.list[id.0].0.take()
+    }
+
+    pub fn iter (&self) -> impl Iterator<Item = &T> {
+        self.list.iter().filter_map(|item_data| item_data.0.as_ref())
+    }
+
+    pub fn ids (&self) -> Vec<ArenaID> {
+        self.list.iter().enumerate()
+            .filter(|(_, item_data)| item_data.0.is_some())
+            .map(|(i, item_data)| (i, item_data.1))
+            .collect()
+    }
+
+}
+
+impl<T> Default for IdArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
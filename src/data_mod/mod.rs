@@ -1,4 +1,12 @@
 pub mod general_data;
 pub mod cell_data;
+pub mod connection_data;
+pub mod organism_data;
+pub mod genome;
+pub mod controller;
 pub mod entity_container;
-pub mod errors;
\ No newline at end of file
+pub mod id_arena;
+pub mod errors;
+pub mod rng;
+pub mod snapshot;
+pub mod settings;
@@ -0,0 +1,114 @@
+use crate::prelude::*;
+
+
+
+//-------------------------------------------------------------------------------------------------//
+// WARNING: Cell.organism_id and Organism.cell_ids are only updated by update::update_organisms(), //
+// so they can be a frame out of date during the main update pass                                  //
+//-------------------------------------------------------------------------------------------------//
+
+pub type OrganismID = ArenaID;
+
+// every group of connected cells is an organism
+pub struct OrganismContainer {
+    pub arena: IdArena<Organism>,
+    pub split_count: u64, // since the world was created
+    pub merge_count: u64, // since the world was created
+}
+
+
+
+#[derive(Debug, Clone)]
+pub struct Organism {
+    pub cell_ids: Vec<EntityID>,
+    pub energy: f64, // includes the energy stored in fat & egg cells
+    pub material: f64, // includes the material stored in fat & egg cells
+    pub center_of_mass: (f64, f64),
+    pub bounding_box: ((f64, f64), (f64, f64)), // min & max cell positions, can go past the world's edges when the world wraps
+    pub radius: f64, // distance from the center of mass to the furthest cell edge
+    pub age: f64, // in seconds
+    pub parent_id: Option<OrganismID>, // the organism this one split off from
+}
+
+impl Organism {
+
+    pub fn new (parent_id: Option<OrganismID>) -> Self {
+        Self {
+            cell_ids: vec!(),
+            energy: 0.,
+            material: 0.,
+            center_of_mass: (0., 0.),
+            bounding_box: ((0., 0.), (0., 0.)),
+            radius: 0.5,
+            age: 0.,
+            parent_id,
+        }
+    }
+
+    // positions are measured from the first cell so that organisms crossing a wrapped edge stay in one piece
+    pub fn set_cells (&mut self, cell_ids: Vec<EntityID>, cells: &EntityContainer<Cell>) {
+        let first_cell = cells.get(cell_ids[0]).unwrap();
+        let first_pos = (first_cell.entity.x, first_cell.entity.y);
+        let (mut energy, mut material) = (0., 0.);
+        let mut pos_total = (0., 0.);
+        let (mut min, mut max) = (first_pos, first_pos);
+        for &cell_id in &cell_ids {
+            let cell = cells.get(cell_id).unwrap();
+            energy += cell.get_total_energy();
+            material += cell.get_total_material();
+            let pos_change = cells.size.get_pos_change(first_pos, (cell.entity.x, cell.entity.y));
+            let pos = (first_pos.0 + pos_change.0, first_pos.1 + pos_change.1);
+            pos_total = (pos_total.0 + pos.0, pos_total.1 + pos.1);
+            min = (min.0.min(pos.0), min.1.min(pos.1));
+            max = (max.0.max(pos.0), max.1.max(pos.1));
+        }
+        let cell_count = cell_ids.len() as f64;
+        let center = (pos_total.0 / cell_count, pos_total.1 / cell_count);
+        self.radius = cell_ids.iter()
+            .map(|&cell_id| {
+                let cell = cells.get(cell_id).unwrap();
+                fns::vec_len(cells.size.get_pos_change(center, (cell.entity.x, cell.entity.y))) + 0.5
+            })
+            .fold(0.5, f64::max);
+        self.center_of_mass = cells.size.constrain_pos(center);
+        self.bounding_box = (min, max);
+        self.energy = energy;
+        self.material = material;
+        self.cell_ids = cell_ids;
+    }
+
+}
+
+
+
+
+
+impl OrganismContainer {
+    pub fn new() -> Self {
+        Self {
+            arena: IdArena::new(),
+            split_count: 0,
+            merge_count: 0,
+        }
+    }
+}
+
+impl Default for OrganismContainer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// organisms are looked up like any other arena, the container only adds the split & merge counts
+impl std::ops::Deref for OrganismContainer {
+    type Target = IdArena<Organism>;
+    fn deref (&self) -> &Self::Target {
+        &self.arena
+    }
+}
+
+impl std::ops::DerefMut for OrganismContainer {
+    fn deref_mut (&mut self) -> &mut Self::Target {
+        &mut self.arena
+    }
+}
@@ -0,0 +1,54 @@
+use crate::prelude::*;
+
+
+
+// splitmix64, see https://prng.di.unimi.it/splitmix64.c
+// every rng is derived from the world seed + frame + entity index so that results don't depend on which thread handles which cell
+
+#[derive(Debug, Clone)]
+pub struct SimRng {
+    pub state: u64,
+}
+
+impl SimRng {
+
+    pub fn new (seed: u64) -> Self {
+        Self {
+            state: seed,
+        }
+    }
+
+    pub fn new_for_entity (seed: u64, frame_count: u64, entity_index: usize) -> Self {
+        let mut rng = Self::new(seed);
+        rng.state ^= Self::new(frame_count).next_u64();
+        rng.state ^= Self::new(entity_index as u64).next_u64().rotate_left(32);
+        rng
+    }
+
+    pub fn next_u64 (&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // returns a value in [0, 1)
+    pub fn next_f64 (&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn range_f64 (&mut self, min: f64, max: f64) -> f64 {
+        min + self.next_f64() * (max - min)
+    }
+
+    // returns a value in [0, max)
+    pub fn range_usize (&mut self, max: usize) -> usize {
+        (self.next_u64() % max as u64) as usize
+    }
+
+    pub fn chance (&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+}
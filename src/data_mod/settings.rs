@@ -0,0 +1,572 @@
+use crate::prelude::*;
+
+
+
+// Settings files are plain text, one setting per line:
+//   cell_drag_coef = 0.1
+//   fat_energy_store_threshold = 0.0, 1.0, 0.75   (ranges are min, max, default)
+// Lines starting with '#' are ignored, and any setting that isn't listed keeps its default value
+
+pub const SETTINGS_FILE_NAME: &str = "settings.txt";
+pub const SETTINGS_CHECK_INTERVAL: f64 = 0.5;
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimSettings {
+
+    // General Settings
+    pub warmup_frames: u64,
+
+    // Cell Settings
+    pub cell_drag_coef: f64,
+    pub cell_connection_force: f64,
+    pub cell_connection_drag: f64,
+    pub cell_connection_distance: f64,
+    pub cell_intersection_force: f64,
+    pub connection_rest_length_scale: SettingRange, // per-connection multipliers, mutated in genomes
+    pub connection_stiffness_scale: SettingRange,
+    pub connection_damping_scale: SettingRange,
+    pub connection_permeability: SettingRange,
+
+    pub connection_max_stretch: f64,
+    pub connection_max_compression: f64,
+    pub connection_stress_threshold: f64,
+    pub connection_stress_damage: f64,
+    pub connection_healing_rate: f64,
+
+    pub cell_energy_use_rate: f64,
+    pub cell_healing_rate: f64,
+    pub cell_healing_energy_cost: f64,
+    pub cell_healing_material_cost: f64,
+    pub cell_energy_transfer_rate: f64,
+    pub cell_energy_transfer_threshold: f64,
+    pub cell_material_transfer_rate: f64,
+    pub cell_material_transfer_threshold: f64,
+    pub signal_diffusion_rate: f64,
+    pub signal_decay_rate: f64,
+    pub signal_emission_rate: f64, // multiplies the controllers' emit signal outputs
+    pub sensor_signal_emission_rate: f64, // multiplies RawCell::get_signal_emission()
+
+    // Cell Type Settings
+    pub fat_energy_store_threshold: SettingRange,
+    pub fat_energy_release_threshold: SettingRange,
+    pub fat_energy_store_rate: SettingRange,
+    pub fat_energy_release_rate: SettingRange,
+    pub fat_material_store_threshold: SettingRange,
+    pub fat_material_release_threshold: SettingRange,
+    pub fat_material_store_rate: SettingRange,
+    pub fat_material_release_rate: SettingRange,
+
+    pub photosynthesiser_rate: f64,
+    pub light_level_top: f64, // the light level goes from light_level_top at y = 0 to light_level_bottom at the bottom of the world
+    pub light_level_bottom: f64,
+
+    pub egg_absorb_rate: f64,
+    pub egg_energy_absorb_threshold: f64,
+
+    pub mouth_eat_rate: f64,
+
+    pub flagellum_strength: SettingRange,
+    pub flagellum_frequency: SettingRange,
+    pub flagellum_energy_cost: f64,
+
+    pub contractor_contraction: SettingRange,
+    pub contractor_frequency: SettingRange,
+    pub contractor_energy_cost: f64,
+
+    pub food_sensor_range: SettingRange,
+
+    pub adhesion_energy_cost: f64,
+    pub adhesion_max_connections: u64,
+    pub adhesion_same_organism_only: bool,
+
+    pub spike_damage: SettingRange,
+    pub spike_energy_cost: f64, // per point of damage dealt
+
+    pub armor_damage_reduction: SettingRange,
+    pub armor_drag_mult: f64, // multiplies cell_drag_coef
+    pub armor_energy_upkeep: f64, // per second, at full damage reduction
+    pub armor_material_upkeep: f64, // per second, at full damage reduction
+
+    // Reproduction Settings
+    pub reproduction_cell_energy: f64,
+    pub reproduction_cell_material: f64,
+    pub reproduction_energy_reserve: f64,
+
+    // Mutation Settings
+    pub mutation_parameter_chance: f64,
+    pub mutation_parameter_amount: f64,
+    pub mutation_cell_type_chance: f64,
+    pub mutation_add_cell_chance: f64,
+    pub mutation_remove_cell_chance: f64,
+    pub mutation_add_connection_chance: f64,
+    pub mutation_remove_connection_chance: f64,
+    pub mutation_controller_chance: f64,
+    pub mutation_controller_amount: f64,
+    pub controller_max_weight: f64,
+    pub mutation_history_max_len: u64,
+
+}
+
+impl Default for SimSettings {
+    fn default() -> Self {
+        Self {
+
+            warmup_frames: 30,
+
+            cell_drag_coef: 0.1,
+            cell_connection_force: 10.0,
+            cell_connection_drag: 3.0,
+            cell_connection_distance: 1.1,
+            cell_intersection_force: 50.0,
+            connection_rest_length_scale: SettingRange::new(0.5, 2.0, 1.0),
+            connection_stiffness_scale:   SettingRange::new(0.25, 4.0, 1.0),
+            connection_damping_scale:     SettingRange::new(0.25, 4.0, 1.0),
+            connection_permeability:      SettingRange::new(0.0, 2.0, 1.0),
+
+            connection_max_stretch: 1.5,
+            connection_max_compression: 0.75,
+            connection_stress_threshold: 0.5,
+            connection_stress_damage: 2.0,
+            connection_healing_rate: 0.1,
+
+            cell_energy_use_rate: 0.001,
+            cell_healing_rate: 0.1,
+            cell_healing_energy_cost: 0.2,
+            cell_healing_material_cost: 0.5,
+            cell_energy_transfer_rate: 0.25,
+            cell_energy_transfer_threshold: 0.025,
+            cell_material_transfer_rate: 0.1,
+            cell_material_transfer_threshold: 0.025,
+            signal_diffusion_rate: 0.5,
+            signal_decay_rate: 0.5,
+            signal_emission_rate: 1.0,
+            sensor_signal_emission_rate: 1.0,
+
+            fat_energy_store_threshold:     SettingRange::new(0.0, 1.0, 0.75),
+            fat_energy_release_threshold:   SettingRange::new(0.0, 1.0, 0.5),
+            fat_energy_store_rate:          SettingRange::new(0.0, 0.2, 0.1),
+            fat_energy_release_rate:        SettingRange::new(0.0, 0.2, 0.1),
+            fat_material_store_threshold:   SettingRange::new(0.0, 1.0, 0.75),
+            fat_material_release_threshold: SettingRange::new(0.0, 1.0, 0.5),
+            fat_material_store_rate:        SettingRange::new(0.0, 0.2, 0.1),
+            fat_material_release_rate:      SettingRange::new(0.0, 0.2, 0.1),
+
+            photosynthesiser_rate: 0.025,
+            light_level_top: 1.0,
+            light_level_bottom: 1.0,
+
+            egg_absorb_rate: 0.05,
+            egg_energy_absorb_threshold: 0.25,
+
+            mouth_eat_rate: 0.1,
+
+            flagellum_strength:  SettingRange::new(0.0, 5.0, 2.0),
+            flagellum_frequency: SettingRange::new(0.0, 2.0, 0.5),
+            flagellum_energy_cost: 0.01,
+
+            contractor_contraction: SettingRange::new(0.0, 0.5, 0.25),
+            contractor_frequency:   SettingRange::new(0.0, 2.0, 0.5),
+            contractor_energy_cost: 0.02,
+
+            food_sensor_range: SettingRange::new(1.0, 8.0, 4.0),
+
+            adhesion_energy_cost: 0.05,
+            adhesion_max_connections: 4,
+            adhesion_same_organism_only: false,
+
+            spike_damage: SettingRange::new(0.0, 1.0, 0.5),
+            spike_energy_cost: 0.5,
+
+            armor_damage_reduction: SettingRange::new(0.0, 0.9, 0.5),
+            armor_drag_mult: 3.0,
+            armor_energy_upkeep: 0.01,
+            armor_material_upkeep: 0.005,
+
+            reproduction_cell_energy: 0.25,
+            reproduction_cell_material: 0.25,
+            reproduction_energy_reserve: 0.25,
+
+            mutation_parameter_chance: 0.05,
+            mutation_parameter_amount: 0.1,
+            mutation_cell_type_chance: 0.01,
+            mutation_add_cell_chance: 0.02,
+            mutation_remove_cell_chance: 0.02,
+            mutation_add_connection_chance: 0.02,
+            mutation_remove_connection_chance: 0.02,
+            mutation_controller_chance: 0.05,
+            mutation_controller_amount: 0.25,
+            controller_max_weight: 4.0,
+            mutation_history_max_len: 100,
+
+        }
+    }
+}
+
+
+
+impl SimSettings {
+
+    // every setting has to be listed here to be read from / written to settings files, along with the values it's allowed to have
+    pub fn visit_settings (&mut self, mut visit_fn: impl FnMut(&'static str, SettingMut, SettingBounds)) {
+
+        visit_fn("warmup_frames", SettingMut::U64 (&mut self.warmup_frames), SettingBounds::Any);
+
+        visit_fn("cell_drag_coef", SettingMut::F64 (&mut self.cell_drag_coef), SettingBounds::NonNegative);
+        visit_fn("cell_connection_force", SettingMut::F64 (&mut self.cell_connection_force), SettingBounds::NonNegative);
+        visit_fn("cell_connection_drag", SettingMut::F64 (&mut self.cell_connection_drag), SettingBounds::NonNegative);
+        visit_fn("cell_connection_distance", SettingMut::F64 (&mut self.cell_connection_distance), SettingBounds::Positive);
+        visit_fn("cell_intersection_force", SettingMut::F64 (&mut self.cell_intersection_force), SettingBounds::NonNegative);
+        visit_fn("connection_rest_length_scale", SettingMut::Range (&mut self.connection_rest_length_scale), SettingBounds::Positive);
+        visit_fn("connection_stiffness_scale", SettingMut::Range (&mut self.connection_stiffness_scale), SettingBounds::NonNegative);
+        visit_fn("connection_damping_scale", SettingMut::Range (&mut self.connection_damping_scale), SettingBounds::NonNegative);
+        visit_fn("connection_permeability", SettingMut::Range (&mut self.connection_permeability), SettingBounds::NonNegative);
+
+        visit_fn("connection_max_stretch", SettingMut::F64 (&mut self.connection_max_stretch), SettingBounds::Positive);
+        visit_fn("connection_max_compression", SettingMut::F64 (&mut self.connection_max_compression), SettingBounds::NonNegative);
+        visit_fn("connection_stress_threshold", SettingMut::F64 (&mut self.connection_stress_threshold), SettingBounds::NonNegative);
+        visit_fn("connection_stress_damage", SettingMut::F64 (&mut self.connection_stress_damage), SettingBounds::NonNegative);
+        visit_fn("connection_healing_rate", SettingMut::F64 (&mut self.connection_healing_rate), SettingBounds::NonNegative);
+
+        visit_fn("cell_energy_use_rate", SettingMut::F64 (&mut self.cell_energy_use_rate), SettingBounds::NonNegative);
+        visit_fn("cell_healing_rate", SettingMut::F64 (&mut self.cell_healing_rate), SettingBounds::NonNegative);
+        visit_fn("cell_healing_energy_cost", SettingMut::F64 (&mut self.cell_healing_energy_cost), SettingBounds::NonNegative);
+        visit_fn("cell_healing_material_cost", SettingMut::F64 (&mut self.cell_healing_material_cost), SettingBounds::NonNegative);
+        visit_fn("cell_energy_transfer_rate", SettingMut::F64 (&mut self.cell_energy_transfer_rate), SettingBounds::NonNegative);
+        visit_fn("cell_energy_transfer_threshold", SettingMut::F64 (&mut self.cell_energy_transfer_threshold), SettingBounds::NonNegative);
+        visit_fn("cell_material_transfer_rate", SettingMut::F64 (&mut self.cell_material_transfer_rate), SettingBounds::NonNegative);
+        visit_fn("cell_material_transfer_threshold", SettingMut::F64 (&mut self.cell_material_transfer_threshold), SettingBounds::NonNegative);
+        visit_fn("signal_diffusion_rate", SettingMut::F64 (&mut self.signal_diffusion_rate), SettingBounds::NonNegative);
+        visit_fn("signal_decay_rate", SettingMut::F64 (&mut self.signal_decay_rate), SettingBounds::NonNegative);
+        visit_fn("signal_emission_rate", SettingMut::F64 (&mut self.signal_emission_rate), SettingBounds::NonNegative);
+        visit_fn("sensor_signal_emission_rate", SettingMut::F64 (&mut self.sensor_signal_emission_rate), SettingBounds::NonNegative);
+
+        visit_fn("fat_energy_store_threshold", SettingMut::Range (&mut self.fat_energy_store_threshold), SettingBounds::Fraction);
+        visit_fn("fat_energy_release_threshold", SettingMut::Range (&mut self.fat_energy_release_threshold), SettingBounds::Fraction);
+        visit_fn("fat_energy_store_rate", SettingMut::Range (&mut self.fat_energy_store_rate), SettingBounds::NonNegative);
+        visit_fn("fat_energy_release_rate", SettingMut::Range (&mut self.fat_energy_release_rate), SettingBounds::NonNegative);
+        visit_fn("fat_material_store_threshold", SettingMut::Range (&mut self.fat_material_store_threshold), SettingBounds::Fraction);
+        visit_fn("fat_material_release_threshold", SettingMut::Range (&mut self.fat_material_release_threshold), SettingBounds::Fraction);
+        visit_fn("fat_material_store_rate", SettingMut::Range (&mut self.fat_material_store_rate), SettingBounds::NonNegative);
+        visit_fn("fat_material_release_rate", SettingMut::Range (&mut self.fat_material_release_rate), SettingBounds::NonNegative);
+
+        visit_fn("photosynthesiser_rate", SettingMut::F64 (&mut self.photosynthesiser_rate), SettingBounds::NonNegative);
+        visit_fn("light_level_top", SettingMut::F64 (&mut self.light_level_top), SettingBounds::NonNegative);
+        visit_fn("light_level_bottom", SettingMut::F64 (&mut self.light_level_bottom), SettingBounds::NonNegative);
+
+        visit_fn("egg_absorb_rate", SettingMut::F64 (&mut self.egg_absorb_rate), SettingBounds::NonNegative);
+        visit_fn("egg_energy_absorb_threshold", SettingMut::F64 (&mut self.egg_energy_absorb_threshold), SettingBounds::NonNegative);
+
+        visit_fn("mouth_eat_rate", SettingMut::F64 (&mut self.mouth_eat_rate), SettingBounds::NonNegative);
+
+        visit_fn("flagellum_strength", SettingMut::Range (&mut self.flagellum_strength), SettingBounds::NonNegative);
+        visit_fn("flagellum_frequency", SettingMut::Range (&mut self.flagellum_frequency), SettingBounds::NonNegative);
+        visit_fn("flagellum_energy_cost", SettingMut::F64 (&mut self.flagellum_energy_cost), SettingBounds::NonNegative);
+
+        visit_fn("contractor_contraction", SettingMut::Range (&mut self.contractor_contraction), SettingBounds::Contraction);
+        visit_fn("contractor_frequency", SettingMut::Range (&mut self.contractor_frequency), SettingBounds::NonNegative);
+        visit_fn("contractor_energy_cost", SettingMut::F64 (&mut self.contractor_energy_cost), SettingBounds::NonNegative);
+
+        visit_fn("food_sensor_range", SettingMut::Range (&mut self.food_sensor_range), SettingBounds::NonNegative);
+
+        visit_fn("adhesion_energy_cost", SettingMut::F64 (&mut self.adhesion_energy_cost), SettingBounds::NonNegative);
+        visit_fn("adhesion_max_connections", SettingMut::U64 (&mut self.adhesion_max_connections), SettingBounds::Any);
+        visit_fn("adhesion_same_organism_only", SettingMut::Bool (&mut self.adhesion_same_organism_only), SettingBounds::Any);
+
+        visit_fn("spike_damage", SettingMut::Range (&mut self.spike_damage), SettingBounds::NonNegative);
+        visit_fn("spike_energy_cost", SettingMut::F64 (&mut self.spike_energy_cost), SettingBounds::NonNegative);
+
+        visit_fn("armor_damage_reduction", SettingMut::Range (&mut self.armor_damage_reduction), SettingBounds::Fraction);
+        visit_fn("armor_drag_mult", SettingMut::F64 (&mut self.armor_drag_mult), SettingBounds::NonNegative);
+        visit_fn("armor_energy_upkeep", SettingMut::F64 (&mut self.armor_energy_upkeep), SettingBounds::NonNegative);
+        visit_fn("armor_material_upkeep", SettingMut::F64 (&mut self.armor_material_upkeep), SettingBounds::NonNegative);
+
+        visit_fn("reproduction_cell_energy", SettingMut::F64 (&mut self.reproduction_cell_energy), SettingBounds::NonNegative);
+        visit_fn("reproduction_cell_material", SettingMut::F64 (&mut self.reproduction_cell_material), SettingBounds::NonNegative);
+        visit_fn("reproduction_energy_reserve", SettingMut::F64 (&mut self.reproduction_energy_reserve), SettingBounds::NonNegative);
+
+        visit_fn("mutation_parameter_chance", SettingMut::F64 (&mut self.mutation_parameter_chance), SettingBounds::Fraction);
+        visit_fn("mutation_parameter_amount", SettingMut::F64 (&mut self.mutation_parameter_amount), SettingBounds::NonNegative);
+        visit_fn("mutation_cell_type_chance", SettingMut::F64 (&mut self.mutation_cell_type_chance), SettingBounds::Fraction);
+        visit_fn("mutation_add_cell_chance", SettingMut::F64 (&mut self.mutation_add_cell_chance), SettingBounds::Fraction);
+        visit_fn("mutation_remove_cell_chance", SettingMut::F64 (&mut self.mutation_remove_cell_chance), SettingBounds::Fraction);
+        visit_fn("mutation_add_connection_chance", SettingMut::F64 (&mut self.mutation_add_connection_chance), SettingBounds::Fraction);
+        visit_fn("mutation_remove_connection_chance", SettingMut::F64 (&mut self.mutation_remove_connection_chance), SettingBounds::Fraction);
+        visit_fn("mutation_controller_chance", SettingMut::F64 (&mut self.mutation_controller_chance), SettingBounds::Fraction);
+        visit_fn("mutation_controller_amount", SettingMut::F64 (&mut self.mutation_controller_amount), SettingBounds::NonNegative);
+        visit_fn("controller_max_weight", SettingMut::F64 (&mut self.controller_max_weight), SettingBounds::Positive);
+        visit_fn("mutation_history_max_len", SettingMut::U64 (&mut self.mutation_history_max_len), SettingBounds::Any);
+
+    }
+
+
+
+    pub fn get_default_path() -> PathBuf {
+        let mut path = fns::get_program_dir();
+        path.push(SETTINGS_FILE_NAME);
+        path
+    }
+
+    // creates the file with the default settings if it doesn't exist yet, so there's a file to edit
+    // not being able to create it (for example in a read-only install dir) isn't an error, the defaults are still used
+    pub fn load_or_create (path: &Path) -> Result<Self, ProgramError> {
+        if !path.exists() {
+            let settings = Self::default();
+            if let Err(error) = fs::write(path, settings.to_settings_string()) {
+                eprintln!("Warning: could not create the settings file {path:?} ({error}), using the default settings");
+            }
+            return Ok(settings);
+        }
+        Self::load(path)
+    }
+
+    // like load_or_create(), but never writes anything, for runs that shouldn't touch the filesystem
+    pub fn load_or_default (path: &Path) -> Result<Self, ProgramError> {
+        if !path.exists() {return Ok(Self::default());}
+        Self::load(path)
+    }
+
+    pub fn load (path: &Path) -> Result<Self, ProgramError> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_settings_string(&contents)
+    }
+
+
+
+    pub fn from_settings_string (input: &str) -> Result<Self, ProgramError> {
+        let mut errors = vec!();
+
+        // get raw values
+        let mut raw_values: HashMap<&str, (usize, &str)> = HashMap::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {continue;}
+            let Some((name, value)) = line.split_once('=') else {
+                errors.push(format!("line {}: expected \"name = value\"", i + 1));
+                continue;
+            };
+            if let Some((first_line_num, _)) = raw_values.get(name.trim()) {
+                errors.push(format!("line {}: \"{}\" is already set on line {first_line_num}", i + 1, name.trim()));
+                continue;
+            }
+            raw_values.insert(name.trim(), (i + 1, value.trim()));
+        }
+
+        // parse values
+        let mut output = Self::default();
+        output.visit_settings(|name, setting, _| {
+            let Some((line_num, raw_value)) = raw_values.remove(name) else {return;};
+            if let Err(error) = setting.parse_into(raw_value) {
+                errors.push(format!("line {line_num}: {name}: {error}"));
+            }
+        });
+        let mut unknown_settings = raw_values.into_iter().collect::<Vec<_>>();
+        unknown_settings.sort_by_key(|(_, (line_num, _))| *line_num);
+        for (name, (line_num, _)) in unknown_settings {
+            errors.push(format!("line {line_num}: unknown setting \"{name}\""));
+        }
+
+        errors.append(&mut output.get_validation_errors());
+        if !errors.is_empty() {
+            return Err(ProgramError::InvalidSettings (errors.join("\n")));
+        }
+        Ok(output)
+    }
+
+    pub fn to_settings_string (&self) -> String {
+        let mut output = String::new();
+        self.clone().visit_settings(|name, setting, _| {
+            output += &format!("{name} = {}\n", setting.to_settings_string());
+        });
+        output
+    }
+
+
+
+    pub fn get_validation_errors (&self) -> Vec<String> {
+        let mut errors = vec!();
+        self.clone().visit_settings(|name, setting, bounds| {
+            if let Err(error) = setting.validate(bounds) {
+                errors.push(format!("{name}: {error}"));
+            }
+        });
+        errors
+    }
+
+}
+
+
+
+
+
+// polls the settings file's modified time and reloads it when it changes
+pub struct SettingsWatcher {
+    pub path: PathBuf,
+    pub last_modified: Option<SystemTime>,
+    pub last_check_instant: Instant,
+    pub error: Option<String>,
+}
+
+impl SettingsWatcher {
+
+    pub fn new (path: PathBuf) -> Self {
+        let last_modified = Self::get_modified_time(&path);
+        Self {
+            path,
+            last_modified,
+            last_check_instant: Instant::now(),
+            error: None,
+        }
+    }
+
+    pub fn get_modified_time (path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    // returns the new settings if the file changed and is valid, errors are kept in self.error instead of being returned
+    pub fn check_for_changes (&mut self) -> Option<SimSettings> {
+        if self.last_check_instant.elapsed().as_secs_f64() < SETTINGS_CHECK_INTERVAL {return None;}
+        self.last_check_instant = Instant::now();
+
+        let modified = Self::get_modified_time(&self.path);
+        if modified == self.last_modified {return None;}
+        self.last_modified = modified;
+
+        match SimSettings::load(&self.path) {
+            Ok(settings) => {
+                self.error = None;
+                Some(settings)
+            }
+            Err(ProgramError::InvalidSettings (error)) => {
+                self.error = Some(error);
+                None
+            }
+            Err(error) => {
+                self.error = Some(format!("{error:?}"));
+                None
+            }
+        }
+    }
+
+}
+
+
+
+
+
+// (min, max, default) for values that can differ between cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SettingRange {
+    pub min: f64,
+    pub max: f64,
+    pub default: f64,
+}
+
+impl SettingRange {
+    pub const fn new (min: f64, max: f64, default: f64) -> Self {
+        Self {
+            min,
+            max,
+            default,
+        }
+    }
+    pub fn clamp (&self, value: f64) -> f64 {
+        value.clamp(self.min, self.max)
+    }
+}
+
+
+
+pub enum SettingMut<'a> {
+    Bool (&'a mut bool),
+    U64 (&'a mut u64),
+    F64 (&'a mut f64),
+    Range (&'a mut SettingRange),
+}
+
+impl<'a> SettingMut<'a> {
+
+    pub fn parse_into (self, input: &str) -> Result<(), String> {
+        match self {
+            Self::Bool (value) => {
+                *value = input.parse().map_err(|_| format!("\"{input}\" is not true or false"))?;
+            }
+            Self::U64 (value) => {
+                *value = input.parse().map_err(|_| format!("\"{input}\" is not a positive integer"))?;
+            }
+            Self::F64 (value) => {
+                *value = parse_setting_f64(input)?;
+            }
+            Self::Range (range) => {
+                let parts = input.split(',').collect::<Vec<&str>>();
+                if parts.len() != 3 {return Err(format!("expected \"min, max, default\", got \"{input}\""));}
+                range.min = parse_setting_f64(parts[0])?;
+                range.max = parse_setting_f64(parts[1])?;
+                range.default = parse_setting_f64(parts[2])?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_settings_string (&self) -> String {
+        match self {
+            Self::Bool (value) => value.to_string(),
+            Self::U64 (value) => value.to_string(),
+            Self::F64 (value) => value.to_string(),
+            Self::Range (range) => format!("{}, {}, {}", range.min, range.max, range.default),
+        }
+    }
+
+    pub fn validate (&self, bounds: SettingBounds) -> Result<(), String> {
+        match self {
+            Self::Bool (_) => {}
+            Self::U64 (_) => {}
+            Self::F64 (value) => {
+                if !value.is_finite() {return Err(format!("value must be finite, got {value}"));}
+                bounds.check(**value)?;
+            }
+            Self::Range (range) => {
+                if !range.min.is_finite() || !range.max.is_finite() || !range.default.is_finite() {
+                    return Err(String::from("range values must be finite"));
+                }
+                if range.min > range.max {return Err(format!("min ({}) is greater than max ({})", range.min, range.max));}
+                if range.default < range.min || range.default > range.max {
+                    return Err(format!("default ({}) is not between min ({}) and max ({})", range.default, range.min, range.max));
+                }
+                // the default is between min and max, so it doesn't need its own check
+                bounds.check(range.min)?;
+                bounds.check(range.max)?;
+            }
+        }
+        Ok(())
+    }
+
+}
+
+// the values a setting is allowed to have (on top of being finite), for ranges this applies to min, max and default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingBounds {
+    Any,
+    NonNegative,
+    Positive,
+    Fraction, // 0 to 1, used for chances & thresholds that are compared to values between 0 and 1
+    Contraction, // 0 to MAX_CONTRACTION, so that a contracted connection never has a rest length of 0
+}
+
+impl SettingBounds {
+    pub fn check (&self, value: f64) -> Result<(), String> {
+        match self {
+            Self::NonNegative if value < 0. => Err(format!("value must not be negative, got {value}")),
+            Self::Positive if value <= 0. => Err(format!("value must be greater than 0, got {value}")),
+            Self::Fraction if !(0. ..= 1.).contains(&value) => Err(format!("value must be between 0 and 1, got {value}")),
+            Self::Contraction if !(0. ..= MAX_CONTRACTION).contains(&value) => Err(format!("value must be between 0 and {MAX_CONTRACTION}, got {value}")),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn parse_setting_f64 (input: &str) -> Result<f64, String> {
+    let input = input.trim();
+    input.parse().map_err(|_| format!("\"{input}\" is not a number"))
+}
@@ -0,0 +1,1103 @@
+use crate::prelude::*;
+
+
+
+// File layout: SNAPSHOT_MAGIC, version (u32), then the world and camera
+// When a field is added, bump SNAPSHOT_VERSION and only read the new field if the file's version is high enough
+
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"CELLSNAP";
+pub const SNAPSHOT_VERSION: u32 = 11;
+
+// Version history:
+// 1: initial format
+// 2: added the world size (version 1 worlds are always DEFAULT_GRID_WIDTH x DEFAULT_GRID_HEIGHT)
+// 3: added the boundary mode (older worlds always have walls)
+// 4: added cell genomes, saved once per world in a genome table (older cells have no genome)
+// 5: added genome generations and mutation histories
+// 6: added per-connection rest length and stiffness scales, in both cells and genomes (older connections use 1.0)
+// 7: moved connections out of the cells and into the world's connection list (older connections are converted when loading),
+//    and added connection health, damping and permeability (genome connections get damping and permeability too)
+// 8: added organisms (older worlds get new organisms when loading)
+// 9: added controllers to genome cells and controller outputs to cells (older cells use the default controller),
+//    and the ChangeController mutation type
+// 10: added cell sensor values
+// 11: added cell signals
+
+
+
+pub fn save_world (world: &World, camera: &Camera, path: &Path) -> Result<(), ProgramError> {
+    let mut writer = SnapshotWriter::new();
+    writer.write_bytes(SNAPSHOT_MAGIC);
+    writer.write_u32(SNAPSHOT_VERSION);
+    world.write_snapshot(&mut writer);
+    camera.write_snapshot(&mut writer);
+    fs::write(path, writer.output)?;
+    Ok(())
+}
+
+// settings aren't part of snapshots, so the loaded world uses the given settings
+pub fn load_world (path: &Path, settings: SimSettings) -> Result<(World, Camera), ProgramError> {
+    let data = fs::read(path)?;
+    let mut reader = SnapshotReader::new(&data);
+    if reader.read_bytes(SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC {
+        return Err(ProgramError::InvalidSnapshot (String::from("file is not a world snapshot")));
+    }
+    reader.version = reader.read_u32()?;
+    if reader.version > SNAPSHOT_VERSION {
+        return Err(ProgramError::InvalidSnapshot (format!("snapshot version {} is newer than the latest supported version ({SNAPSHOT_VERSION})", reader.version)));
+    }
+    let mut world = World::read_snapshot(&mut reader)?;
+    world.settings = settings;
+    let camera = Camera::read_snapshot(&mut reader)?;
+    Ok((world, camera))
+}
+
+
+
+
+
+pub trait Snapshot: Sized {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter);
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError>;
+}
+
+
+
+pub struct SnapshotWriter {
+    pub output: Vec<u8>,
+    pub genome_indices: HashMap<*const Genome, usize>, // indices into the world's genome table, keyed by Arc::as_ptr()
+}
+
+impl SnapshotWriter {
+    pub fn new() -> Self {
+        Self {
+            output: vec!(),
+            genome_indices: HashMap::new(),
+        }
+    }
+    pub fn write_bytes (&mut self, input: &[u8]) {
+        self.output.extend_from_slice(input);
+    }
+    pub fn write_u8 (&mut self, input: u8) {
+        self.output.push(input);
+    }
+    pub fn write_bool (&mut self, input: bool) {
+        self.write_u8(input as u8);
+    }
+    pub fn write_u32 (&mut self, input: u32) {
+        self.write_bytes(&input.to_le_bytes());
+    }
+    pub fn write_u64 (&mut self, input: u64) {
+        self.write_bytes(&input.to_le_bytes());
+    }
+    pub fn write_usize (&mut self, input: usize) {
+        self.write_u64(input as u64);
+    }
+    pub fn write_f64 (&mut self, input: f64) {
+        self.write_bytes(&input.to_le_bytes());
+    }
+    pub fn write_entity_id (&mut self, input: EntityID) {
+        self.write_usize(input.0);
+        self.write_u32(input.1);
+    }
+    pub fn write_string (&mut self, input: &str) {
+        self.write_usize(input.len());
+        self.write_bytes(input.as_bytes());
+    }
+}
+
+impl Default for SnapshotWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+pub struct SnapshotReader<'a> {
+    pub input: &'a [u8],
+    pub position: usize,
+    pub version: u32,
+    pub genomes: Vec<Arc<Genome>>, // the world's genome table, cells get their genome from here
+    pub current_entity_id: EntityID, // the id of the entity being read
+    pub legacy_connections: Vec<Connection>, // connections read from snapshots older than version 7
+}
+
+impl<'a> SnapshotReader<'a> {
+    pub fn new (input: &'a [u8]) -> Self {
+        Self {
+            input,
+            position: 0,
+            version: SNAPSHOT_VERSION,
+            genomes: vec!(),
+            current_entity_id: (0, 0),
+            legacy_connections: vec!(),
+        }
+    }
+    pub fn remaining_len (&self) -> usize {
+        self.input.len() - self.position
+    }
+    pub fn read_bytes (&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        if len > self.remaining_len() {
+            return Err(ProgramError::InvalidSnapshot (format!("unexpected end of file at byte {}", self.position)));
+        }
+        let output = &self.input[self.position .. self.position + len];
+        self.position += len;
+        Ok(output)
+    }
+    pub fn read_u8 (&mut self) -> Result<u8, ProgramError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+    pub fn read_bool (&mut self) -> Result<bool, ProgramError> {
+        Ok(self.read_u8()? != 0)
+    }
+    pub fn read_u32 (&mut self) -> Result<u32, ProgramError> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+    pub fn read_u64 (&mut self) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+    pub fn read_usize (&mut self) -> Result<usize, ProgramError> {
+        Ok(self.read_u64()? as usize)
+    }
+    // nothing saved is ever nan or infinite, and the update would panic on them
+    pub fn read_f64 (&mut self) -> Result<f64, ProgramError> {
+        let output = f64::from_le_bytes(self.read_bytes(8)?.try_into().unwrap());
+        if !output.is_finite() {
+            return Err(ProgramError::InvalidSnapshot (format!("invalid number before byte {}", self.position)));
+        }
+        Ok(output)
+    }
+    // reads a list length and checks that the rest of the file can hold that many items of at least `min_item_size` bytes,
+    // so that a corrupt length fails here instead of causing a huge allocation
+    pub fn read_len (&mut self, min_item_size: usize) -> Result<usize, ProgramError> {
+        let len = self.read_usize()?;
+        match len.checked_mul(min_item_size) {
+            Some(size) if size <= self.remaining_len() => Ok(len),
+            _ => Err(ProgramError::InvalidSnapshot (format!("list length {len} before byte {} is longer than the rest of the file", self.position))),
+        }
+    }
+    pub fn read_entity_id (&mut self) -> Result<EntityID, ProgramError> {
+        Ok((self.read_usize()?, self.read_u32()?))
+    }
+    pub fn read_string (&mut self) -> Result<String, ProgramError> {
+        let len = self.read_usize()?;
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| ProgramError::InvalidSnapshot (format!("invalid string before byte {}", self.position)))
+    }
+}
+
+
+
+
+
+impl Snapshot for World {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_u64(self.frame_count);
+        writer.write_u64(self.seed);
+        // the size is shared by every container, so it's only saved once
+        self.size.write_snapshot(writer);
+        write_genome_table(&self.cells, writer);
+        self.cells.write_snapshot(writer);
+        self.food.write_snapshot(writer);
+        self.connections.write_snapshot(writer);
+        self.organisms.write_snapshot(writer);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let frame_count = reader.read_u64()?;
+        let seed = reader.read_u64()?;
+        let size = if reader.version >= 2 {WorldSize::read_snapshot(reader)?} else {WorldSize::default()};
+        let mut world = World::new(size, SimSettings::default(), seed);
+        world.frame_count = frame_count;
+        if reader.version >= 4 {read_genome_table(reader)?;}
+        world.cells = EntityContainer::read_snapshot(reader, size)?;
+        world.food = EntityContainer::read_snapshot(reader, size)?;
+        if reader.version >= 7 {
+            world.connections = ConnectionContainer::read_snapshot(reader)?;
+        } else {
+            // both cells listed each connection, so only one copy is kept
+            for connection in std::mem::take(&mut reader.legacy_connections) {
+                let (cell_id_1, cell_id_2) = connection.cell_ids;
+                if cell_id_1.0 > cell_id_2.0 || get_entity_checked(&world.cells, cell_id_2).is_none() {continue;}
+                world.connect_cells(connection);
+            }
+        }
+        if reader.version >= 8 {
+            world.organisms = OrganismContainer::read_snapshot(reader)?;
+        }
+        check_world_ids(&world)?;
+        // organisms only save their ids, age and parent, everything else is found again here
+        update::update_organisms(&mut world, 0.);
+        Ok(world)
+    }
+}
+
+
+
+// cells of the same organism share their genome, so each genome is only saved once (in the order the cells first use them)
+// and the cells save an index into this table
+pub fn write_genome_table (cells: &EntityContainer<Cell>, writer: &mut SnapshotWriter) {
+    writer.genome_indices.clear();
+    let mut genomes = vec!();
+    for cell in cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()) {
+        let Some(genome) = &cell.genome else {continue;};
+        writer.genome_indices.entry(Arc::as_ptr(genome)).or_insert_with(|| {
+            genomes.push(genome);
+            genomes.len() - 1
+        });
+    }
+    writer.write_usize(genomes.len());
+    for genome in genomes {
+        genome.write_snapshot(writer);
+    }
+}
+
+pub fn read_genome_table (reader: &mut SnapshotReader) -> Result<(), ProgramError> {
+    let genomes_len = reader.read_len(16)?;
+    let mut genomes = Vec::with_capacity(genomes_len);
+    for _ in 0..genomes_len {
+        genomes.push(Arc::new(Genome::read_snapshot(reader)?));
+    }
+    reader.genomes = genomes;
+    Ok(())
+}
+
+
+
+impl Snapshot for Camera {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.x);
+        writer.write_f64(self.y);
+        writer.write_f64(self.zoom);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            x: reader.read_f64()?,
+            y: reader.read_f64()?,
+            zoom: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for WorldSize {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.width);
+        writer.write_usize(self.height);
+        writer.write_usize(self.max_entities_count);
+        writer.write_u8(match self.boundary_mode {
+            BoundaryMode::Walls => 0,
+            BoundaryMode::Wrap => 1,
+        });
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let width = reader.read_usize()?;
+        let height = reader.read_usize()?;
+        let max_entities_count = reader.read_usize()?;
+        let boundary_mode = if reader.version >= 3 {
+            match reader.read_u8()? {
+                0 => BoundaryMode::Walls,
+                1 => BoundaryMode::Wrap,
+                boundary_mode => return Err(ProgramError::InvalidSnapshot (format!("unknown boundary mode {boundary_mode}"))),
+            }
+        } else {
+            BoundaryMode::Walls
+        };
+        // every grid slot is saved with at least its length, so a real grid can't be bigger than the rest of the file
+        let grid_len = width.saturating_mul(height);
+        if width < MIN_GRID_SIZE || height < MIN_GRID_SIZE || grid_len > reader.remaining_len() / 8 {
+            return Err(ProgramError::InvalidSnapshot (format!("invalid world size {width}x{height}")));
+        }
+        Ok(Self {
+            width,
+            height,
+            max_entities_count,
+            boundary_mode,
+        })
+    }
+}
+
+
+
+// containers don't save their size, it's read once by the world and passed in when loading
+impl<T: Entity + Snapshot + AsRef<RawEntity>> EntityContainer<T> {
+    pub fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.current_index);
+        writer.write_u32(self.empty_slots);
+        writer.write_usize(self.master_list.len());
+        for (entity, generation) in &self.master_list {
+            writer.write_u32(*generation);
+            writer.write_bool(entity.is_some());
+            if let Some(entity) = entity {
+                entity.write_snapshot(writer);
+            }
+        }
+        // the order inside each slot affects the order of nearby-entity checks, so it's saved as-is
+        writer.write_usize(self.entities_by_pos.len());
+        for slot in &self.entities_by_pos {
+            writer.write_usize(slot.len());
+            for &id in slot {
+                writer.write_entity_id(id);
+            }
+        }
+    }
+    pub fn read_snapshot (reader: &mut SnapshotReader, size: WorldSize) -> Result<Self, ProgramError> {
+        let mut container = Self::new(size);
+        container.current_index = reader.read_usize()?;
+        container.empty_slots = reader.read_u32()?;
+        let master_list_len = reader.read_len(5)?;
+        for i in 0..master_list_len {
+            let generation = reader.read_u32()?;
+            reader.current_entity_id = (i, generation);
+            let entity = if reader.read_bool()? {Some(T::read_snapshot(reader)?)} else {None};
+            container.master_list.push((entity, generation));
+        }
+        let grid_len = reader.read_usize()?;
+        if grid_len != container.entities_by_pos.len() {
+            return Err(ProgramError::InvalidSnapshot (format!("snapshot grid has {grid_len} slots, but the world grid has {} slots", container.entities_by_pos.len())));
+        }
+        for slot in &mut container.entities_by_pos {
+            let slot_len = reader.read_len(12)?;
+            for _ in 0..slot_len {
+                slot.push(reader.read_entity_id()?);
+            }
+        }
+        check_entity_container(&container)?;
+        Ok(container)
+    }
+}
+
+
+
+// every live entity has to be listed once, in the grid slot it says it's in, and the free slot data has to match the list
+pub fn check_entity_container<T: Entity + AsRef<RawEntity>> (container: &EntityContainer<T>) -> Result<(), ProgramError> {
+    let invalid = |message: String| Err(ProgramError::InvalidSnapshot (message));
+    let master_list_len = container.master_list.len();
+    if container.current_index >= master_list_len.max(1) {
+        return invalid(format!("entity list index {} is out of range", container.current_index));
+    }
+    let empty_slot_count = container.master_list.iter().filter(|entity_data| entity_data.0.is_none()).count();
+    if container.empty_slots as usize != empty_slot_count {
+        return invalid(format!("entity list says it has {} empty slots, but it has {empty_slot_count}", container.empty_slots));
+    }
+    let mut is_listed = vec![false; master_list_len];
+    for (slot_index, slot) in container.entities_by_pos.iter().enumerate() {
+        let slot_pos = (slot_index % container.size.width, slot_index / container.size.width);
+        for &id in slot {
+            let Some(entity) = get_entity_checked(container, id) else {
+                return invalid(format!("grid slot {slot_pos:?} lists entity {id:?}, which doesn't exist"));
+            };
+            let raw_entity = entity.as_ref();
+            if (raw_entity.current_grid_x, raw_entity.current_grid_y) != slot_pos || is_listed[id.0] {
+                return invalid(format!("entity {id:?} is listed in the wrong grid slot"));
+            }
+            if !(0. .. container.size.width as f64).contains(&raw_entity.x) || !(0. .. container.size.height as f64).contains(&raw_entity.y) {
+                return invalid(format!("entity {id:?} is outside the world"));
+            }
+            is_listed[id.0] = true;
+        }
+    }
+    let live_entity_count = master_list_len - empty_slot_count;
+    if is_listed.iter().filter(|&&listed| listed).count() != live_entity_count {
+        return invalid(String::from("not every entity is listed in the grid"));
+    }
+    Ok(())
+}
+
+// like EntityContainer.get(), but returns None for indices past the end of the list instead of panicking
+pub fn get_entity_checked<T: Entity> (container: &EntityContainer<T>, id: EntityID) -> Option<&T> {
+    if id.0 >= container.master_list.len() {return None;}
+    container.get(id)
+}
+
+// checks the ids that point between entities, these can't be checked until everything has been read
+pub fn check_world_ids (world: &World) -> Result<(), ProgramError> {
+    let invalid = |message: String| Err(ProgramError::InvalidSnapshot (message));
+    let organisms_len = world.organisms.list.len();
+    for (i, cell_data) in world.cells.master_list.iter().enumerate() {
+        let Some(cell) = &cell_data.0 else {continue;};
+        let cell_id = (i, cell_data.1);
+        if cell.organism_id.is_some_and(|organism_id| organism_id.0 >= organisms_len) {
+            return invalid(format!("cell {cell_id:?} has an invalid organism id"));
+        }
+        for &connection_id in &cell.connections {
+            if !world.connections.get(connection_id).is_some_and(|connection| connection.cell_ids.0 == cell_id || connection.cell_ids.1 == cell_id) {
+                return invalid(format!("cell {cell_id:?} lists connection {connection_id:?}, which doesn't connect to it"));
+            }
+        }
+    }
+    // connections can point to dead cells (they're removed at the start of the next update), but not past the end of the cell list
+    for (i, connection_data) in world.connections.list.iter().enumerate() {
+        let Some(connection) = &connection_data.0 else {continue;};
+        let connection_id = (i, connection_data.1);
+        for cell_id in [connection.cell_ids.0, connection.cell_ids.1] {
+            if cell_id.0 >= world.cells.master_list.len() {
+                return invalid(format!("connection {connection_id:?} has an invalid cell id"));
+            }
+            if world.cells.get(cell_id).is_some_and(|cell| !cell.connections.contains(&connection_id)) {
+                return invalid(format!("connection {connection_id:?} isn't listed by its cell {cell_id:?}"));
+            }
+        }
+    }
+    for organism in world.organisms.iter() {
+        if organism.parent_id.is_some_and(|parent_id| parent_id.0 >= organisms_len) {
+            return invalid(String::from("organism has an invalid parent id"));
+        }
+    }
+    check_empty_slots(&world.connections, "connection")?;
+    check_empty_slots(&world.organisms, "organism")?;
+    Ok(())
+}
+
+// IdArena::add() reuses empty slots without checking them
+pub fn check_empty_slots<T> (arena: &IdArena<T>, name: &str) -> Result<(), ProgramError> {
+    if arena.empty_slots.iter().any(|&index| arena.list.get(index).is_none_or(|item_data| item_data.0.is_some())) {
+        return Err(ProgramError::InvalidSnapshot (format!("invalid empty {name} slot")));
+    }
+    Ok(())
+}
+
+
+
+impl Snapshot for RawEntity {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_bool(self.should_be_removed);
+        writer.write_f64(self.x);
+        writer.write_f64(self.y);
+        writer.write_f64(self.width);
+        writer.write_f64(self.height);
+        writer.write_usize(self.current_grid_x);
+        writer.write_usize(self.current_grid_y);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            should_be_removed: reader.read_bool()?,
+            x: reader.read_f64()?,
+            y: reader.read_f64()?,
+            width: reader.read_f64()?,
+            height: reader.read_f64()?,
+            current_grid_x: reader.read_usize()?,
+            current_grid_y: reader.read_usize()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for Food {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.energy);
+        writer.write_f64(self.material);
+        self.entity.write_snapshot(writer);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            energy: reader.read_f64()?,
+            material: reader.read_f64()?,
+            entity: RawEntity::read_snapshot(reader)?,
+        })
+    }
+}
+
+
+
+impl Snapshot for Cell {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_bool(self.is_active);
+        writer.write_f64(self.health);
+        writer.write_f64(self.energy);
+        writer.write_f64(self.material);
+        writer.write_f64(self.x_vel);
+        writer.write_f64(self.y_vel);
+        writer.write_usize(self.connections.len());
+        for &connection_id in &self.connections {
+            writer.write_usize(connection_id.0);
+            writer.write_u32(connection_id.1);
+        }
+        // the genome is saved in the world's genome table (see write_genome_table())
+        writer.write_bool(self.genome.is_some());
+        if let Some(genome) = &self.genome {
+            writer.write_usize(writer.genome_indices[&Arc::as_ptr(genome)]);
+        }
+        writer.write_usize(self.genome_index);
+        writer.write_bool(self.organism_id.is_some());
+        if let Some(organism_id) = self.organism_id {
+            writer.write_usize(organism_id.0);
+            writer.write_u32(organism_id.1);
+        }
+        writer.write_usize(CONTROLLER_OUTPUT_COUNT);
+        for &output in &self.controller_outputs {
+            writer.write_f64(output);
+        }
+        writer.write_f64(self.sensor_value);
+        writer.write_usize(SIGNAL_CHANNEL_COUNT);
+        for &signal in &self.signals {
+            writer.write_f64(signal);
+        }
+        self.raw_cell.write_snapshot(writer);
+        self.entity.write_snapshot(writer);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let is_active = reader.read_bool()?;
+        let health = reader.read_f64()?;
+        let energy = reader.read_f64()?;
+        let material = reader.read_f64()?;
+        let x_vel = reader.read_f64()?;
+        let y_vel = reader.read_f64()?;
+        let connections_len = reader.read_len(12)?;
+        let mut connections = Vec::with_capacity(connections_len);
+        for _ in 0..connections_len {
+            if reader.version >= 7 {
+                connections.push((reader.read_usize()?, reader.read_u32()?));
+                continue;
+            }
+            // older cells stored their own copy of each connection, these are turned into shared connections by World::read_snapshot()
+            let connected_cell_id = reader.read_entity_id()?;
+            let mut connection = Connection::new(reader.current_entity_id, connected_cell_id);
+            if reader.version >= 6 {
+                connection.rest_length_scale = read_rest_length_scale(reader)?;
+                connection.stiffness_scale = reader.read_f64()?;
+            }
+            reader.legacy_connections.push(connection);
+        }
+        let (mut genome, mut genome_index) = (None, 0);
+        if reader.version >= 4 {
+            if reader.read_bool()? {
+                let genome_table_index = reader.read_usize()?;
+                let Some(table_genome) = reader.genomes.get(genome_table_index) else {
+                    return Err(ProgramError::InvalidSnapshot (format!("genome table index {genome_table_index} is out of range")));
+                };
+                genome = Some(table_genome.clone());
+            }
+            genome_index = reader.read_usize()?;
+        }
+        if genome.as_ref().is_some_and(|genome| genome_index >= genome.cells.len()) {
+            return Err(ProgramError::InvalidSnapshot (format!("genome index {genome_index} is out of range")));
+        }
+        let mut organism_id = None;
+        if reader.version >= 8 && reader.read_bool()? {
+            organism_id = Some((reader.read_usize()?, reader.read_u32()?));
+        }
+        let mut controller_outputs = Controller::get_default_outputs();
+        if reader.version >= 9 {
+            read_counted_values(reader, &mut controller_outputs)?;
+        }
+        let sensor_value = if reader.version >= 10 {reader.read_f64()?} else {0.};
+        let mut signals = [0.; SIGNAL_CHANNEL_COUNT];
+        if reader.version >= 11 {
+            read_counted_values(reader, &mut signals)?;
+        }
+        Ok(Self {
+            is_active,
+            health,
+            energy,
+            material,
+            x_vel,
+            y_vel,
+            connections,
+            genome,
+            genome_index,
+            organism_id,
+            controller_outputs,
+            sensor_value,
+            signals,
+            raw_cell: RawCell::read_snapshot(reader)?,
+            entity: RawEntity::read_snapshot(reader)?,
+        })
+    }
+}
+
+
+
+impl<T: Snapshot> Snapshot for IdArena<T> {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.list.len());
+        for (item, generation) in &self.list {
+            writer.write_u32(*generation);
+            writer.write_bool(item.is_some());
+            if let Some(item) = item {
+                item.write_snapshot(writer);
+            }
+        }
+        writer.write_usize(self.empty_slots.len());
+        for &index in &self.empty_slots {
+            writer.write_usize(index);
+        }
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let mut arena = Self::new();
+        let list_len = reader.read_len(5)?;
+        for _ in 0..list_len {
+            let generation = reader.read_u32()?;
+            let item = if reader.read_bool()? {Some(T::read_snapshot(reader)?)} else {None};
+            arena.list.push((item, generation));
+        }
+        let empty_slots_len = reader.read_len(8)?;
+        for _ in 0..empty_slots_len {
+            arena.empty_slots.push(reader.read_usize()?);
+        }
+        Ok(arena)
+    }
+}
+
+
+
+impl Snapshot for Connection {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_entity_id(self.cell_ids.0);
+        writer.write_entity_id(self.cell_ids.1);
+        writer.write_f64(self.rest_length_scale);
+        writer.write_f64(self.stiffness_scale);
+        writer.write_f64(self.damping_scale);
+        writer.write_f64(self.permeability);
+        writer.write_f64(self.health);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            cell_ids: (reader.read_entity_id()?, reader.read_entity_id()?),
+            rest_length_scale: read_rest_length_scale(reader)?,
+            stiffness_scale: reader.read_f64()?,
+            damping_scale: reader.read_f64()?,
+            permeability: reader.read_f64()?,
+            health: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+// a connection can't have a rest length of 0 or less
+pub fn read_rest_length_scale (reader: &mut SnapshotReader) -> Result<f64, ProgramError> {
+    let rest_length_scale = reader.read_f64()?;
+    if !rest_length_scale.is_finite() || rest_length_scale <= 0. {
+        return Err(ProgramError::InvalidSnapshot (format!("invalid connection rest length scale {rest_length_scale}")));
+    }
+    Ok(rest_length_scale)
+}
+
+
+
+impl Snapshot for OrganismContainer {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        self.arena.write_snapshot(writer);
+        writer.write_u64(self.split_count);
+        writer.write_u64(self.merge_count);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            arena: IdArena::read_snapshot(reader)?,
+            split_count: reader.read_u64()?,
+            merge_count: reader.read_u64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for Organism {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.age);
+        writer.write_bool(self.parent_id.is_some());
+        if let Some(parent_id) = self.parent_id {
+            writer.write_usize(parent_id.0);
+            writer.write_u32(parent_id.1);
+        }
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let age = reader.read_f64()?;
+        let parent_id = if reader.read_bool()? {Some((reader.read_usize()?, reader.read_u32()?))} else {None};
+        let mut organism = Organism::new(parent_id);
+        organism.age = age;
+        Ok(organism)
+    }
+}
+
+
+
+// the input & output counts are saved so that adding inputs or outputs doesn't need a new version
+impl Snapshot for Controller {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(CONTROLLER_OUTPUT_COUNT);
+        writer.write_usize(CONTROLLER_INPUT_COUNT);
+        for output_index in 0..CONTROLLER_OUTPUT_COUNT {
+            writer.write_f64(self.biases[output_index]);
+            for &weight in &self.weights[output_index] {
+                writer.write_f64(weight);
+            }
+        }
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let mut controller = Controller::new();
+        let output_count = reader.read_len(8)?;
+        let input_count = reader.read_len(8)?;
+        for output_index in 0..output_count {
+            let bias = reader.read_f64()?;
+            let mut weights = vec!();
+            for _ in 0..input_count {
+                weights.push(reader.read_f64()?);
+            }
+            if output_index >= CONTROLLER_OUTPUT_COUNT {continue;}
+            controller.biases[output_index] = bias;
+            for (weight, value) in controller.weights[output_index].iter_mut().zip(weights) {
+                *weight = value;
+            }
+        }
+        Ok(controller)
+    }
+}
+
+// reads a saved count and that many values, values past the end of `output` are skipped
+pub fn read_counted_values (reader: &mut SnapshotReader, output: &mut [f64]) -> Result<(), ProgramError> {
+    let count = reader.read_len(8)?;
+    for i in 0..count {
+        let value = reader.read_f64()?;
+        if let Some(output_value) = output.get_mut(i) {*output_value = value;}
+    }
+    Ok(())
+}
+
+
+
+impl Snapshot for Genome {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.cells.len());
+        for genome_cell in &self.cells {
+            genome_cell.raw_cell.write_snapshot(writer);
+            writer.write_f64(genome_cell.offset.0);
+            writer.write_f64(genome_cell.offset.1);
+            genome_cell.controller.write_snapshot(writer);
+        }
+        writer.write_usize(self.connections.len());
+        for connection in &self.connections {
+            writer.write_usize(connection.cell_indices.0);
+            writer.write_usize(connection.cell_indices.1);
+            writer.write_f64(connection.rest_length_scale);
+            writer.write_f64(connection.stiffness_scale);
+            writer.write_f64(connection.damping_scale);
+            writer.write_f64(connection.permeability);
+        }
+        writer.write_u64(self.generation);
+        writer.write_usize(self.mutation_history.len());
+        for mutation_record in &self.mutation_history {
+            mutation_record.write_snapshot(writer);
+        }
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let cells_len = reader.read_len(17)?;
+        if cells_len == 0 {
+            return Err(ProgramError::InvalidSnapshot (String::from("genome has no cells")));
+        }
+        let mut cells = Vec::with_capacity(cells_len);
+        for _ in 0..cells_len {
+            let raw_cell = RawCell::read_snapshot(reader)?;
+            let offset = (reader.read_f64()?, reader.read_f64()?);
+            let mut genome_cell = GenomeCell::new(raw_cell, offset);
+            if reader.version >= 9 {genome_cell.controller = Controller::read_snapshot(reader)?;}
+            cells.push(genome_cell);
+        }
+        let connections_len = reader.read_len(16)?;
+        let mut connections = Vec::with_capacity(connections_len);
+        for _ in 0..connections_len {
+            let (cell_index_1, cell_index_2) = (reader.read_usize()?, reader.read_usize()?);
+            if cell_index_1 >= cells_len || cell_index_2 >= cells_len {
+                return Err(ProgramError::InvalidSnapshot (format!("genome connection {:?} is out of range", (cell_index_1, cell_index_2))));
+            }
+            let mut connection = GenomeConnection::new(cell_index_1, cell_index_2);
+            if reader.version >= 6 {
+                connection.rest_length_scale = read_rest_length_scale(reader)?;
+                connection.stiffness_scale = reader.read_f64()?;
+            }
+            if reader.version >= 7 {
+                connection.damping_scale = reader.read_f64()?;
+                connection.permeability = reader.read_f64()?;
+            }
+            connections.push(connection);
+        }
+        let mut genome = Self::new(cells, connections);
+        if reader.version >= 5 {
+            genome.generation = reader.read_u64()?;
+            let mutation_history_len = reader.read_len(9)?;
+            for _ in 0..mutation_history_len {
+                genome.mutation_history.push(MutationRecord::read_snapshot(reader)?);
+            }
+        }
+        Ok(genome)
+    }
+}
+
+
+
+impl Snapshot for MutationRecord {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_u64(self.generation);
+        match &self.mutation {
+            Mutation::ChangeParameter {cell_index, parameter, old_value, new_value} => {
+                writer.write_u8(0);
+                writer.write_usize(*cell_index);
+                writer.write_string(parameter);
+                writer.write_f64(*old_value);
+                writer.write_f64(*new_value);
+            }
+            Mutation::ChangeCellType {cell_index, old_type, new_type} => {
+                writer.write_u8(1);
+                writer.write_usize(*cell_index);
+                writer.write_string(old_type);
+                writer.write_string(new_type);
+            }
+            Mutation::ChangeConnectionParameter {cell_indices, parameter, old_value, new_value} => {
+                writer.write_u8(6);
+                writer.write_usize(cell_indices.0);
+                writer.write_usize(cell_indices.1);
+                writer.write_string(parameter);
+                writer.write_f64(*old_value);
+                writer.write_f64(*new_value);
+            }
+            Mutation::AddCell {cell_index, cell_type} => {
+                writer.write_u8(2);
+                writer.write_usize(*cell_index);
+                writer.write_string(cell_type);
+            }
+            Mutation::RemoveCell {cell_index} => {
+                writer.write_u8(3);
+                writer.write_usize(*cell_index);
+            }
+            Mutation::AddConnection (cell_index_1, cell_index_2) => {
+                writer.write_u8(4);
+                writer.write_usize(*cell_index_1);
+                writer.write_usize(*cell_index_2);
+            }
+            Mutation::RemoveConnection (cell_index_1, cell_index_2) => {
+                writer.write_u8(5);
+                writer.write_usize(*cell_index_1);
+                writer.write_usize(*cell_index_2);
+            }
+            Mutation::ChangeController {cell_index, changed_count, total_change} => {
+                writer.write_u8(7);
+                writer.write_usize(*cell_index);
+                writer.write_usize(*changed_count);
+                writer.write_f64(*total_change);
+            }
+        }
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        let generation = reader.read_u64()?;
+        let mutation = match reader.read_u8()? {
+            0 => Mutation::ChangeParameter {
+                cell_index: reader.read_usize()?,
+                parameter: reader.read_string()?,
+                old_value: reader.read_f64()?,
+                new_value: reader.read_f64()?,
+            },
+            1 => Mutation::ChangeCellType {
+                cell_index: reader.read_usize()?,
+                old_type: reader.read_string()?,
+                new_type: reader.read_string()?,
+            },
+            2 => Mutation::AddCell {
+                cell_index: reader.read_usize()?,
+                cell_type: reader.read_string()?,
+            },
+            3 => Mutation::RemoveCell {cell_index: reader.read_usize()?},
+            4 => Mutation::AddConnection (reader.read_usize()?, reader.read_usize()?),
+            5 => Mutation::RemoveConnection (reader.read_usize()?, reader.read_usize()?),
+            6 => Mutation::ChangeConnectionParameter {
+                cell_indices: (reader.read_usize()?, reader.read_usize()?),
+                parameter: reader.read_string()?,
+                old_value: reader.read_f64()?,
+                new_value: reader.read_f64()?,
+            },
+            7 => Mutation::ChangeController {
+                cell_index: reader.read_usize()?,
+                changed_count: reader.read_usize()?,
+                total_change: reader.read_f64()?,
+            },
+            mutation_type => return Err(ProgramError::InvalidSnapshot (format!("unknown mutation type {mutation_type}"))),
+        };
+        Ok(Self {
+            generation,
+            mutation,
+        })
+    }
+}
+
+
+
+impl Snapshot for RawCell {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        match self {
+            Self::Fat (fat_cell_data) => {
+                writer.write_u8(0);
+                fat_cell_data.write_snapshot(writer);
+            }
+            Self::Photosynthesiser => writer.write_u8(1),
+            Self::Egg (egg_cell_data) => {
+                writer.write_u8(2);
+                egg_cell_data.write_snapshot(writer);
+            }
+            Self::Mouth => writer.write_u8(3),
+            Self::Flagellum (flagellum_cell_data) => {
+                writer.write_u8(4);
+                flagellum_cell_data.write_snapshot(writer);
+            }
+            Self::Contractor (contractor_cell_data) => {
+                writer.write_u8(5);
+                contractor_cell_data.write_snapshot(writer);
+            }
+            Self::Adhesive => writer.write_u8(6),
+            Self::FoodSensor (food_sensor_cell_data) => {
+                writer.write_u8(7);
+                food_sensor_cell_data.write_snapshot(writer);
+            }
+            Self::LightSensor => writer.write_u8(8),
+            Self::ContactSensor => writer.write_u8(9),
+            Self::Spike (spike_cell_data) => {
+                writer.write_u8(10);
+                spike_cell_data.write_snapshot(writer);
+            }
+            Self::Armor (armor_cell_data) => {
+                writer.write_u8(11);
+                armor_cell_data.write_snapshot(writer);
+            }
+        }
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        match reader.read_u8()? {
+            0 => Ok(Self::Fat (FatCellData::read_snapshot(reader)?)),
+            1 => Ok(Self::Photosynthesiser),
+            2 => Ok(Self::Egg (EggCellData::read_snapshot(reader)?)),
+            3 => Ok(Self::Mouth),
+            4 => Ok(Self::Flagellum (FlagellumCellData::read_snapshot(reader)?)),
+            5 => Ok(Self::Contractor (ContractorCellData::read_snapshot(reader)?)),
+            6 => Ok(Self::Adhesive),
+            7 => Ok(Self::FoodSensor (FoodSensorCellData::read_snapshot(reader)?)),
+            8 => Ok(Self::LightSensor),
+            9 => Ok(Self::ContactSensor),
+            10 => Ok(Self::Spike (SpikeCellData::read_snapshot(reader)?)),
+            11 => Ok(Self::Armor (ArmorCellData::read_snapshot(reader)?)),
+            cell_type => Err(ProgramError::InvalidSnapshot (format!("unknown cell type {cell_type}"))),
+        }
+    }
+}
+
+
+
+impl Snapshot for FatCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.extra_energy);
+        writer.write_f64(self.extra_material);
+        writer.write_f64(self.energy_store_threshold);
+        writer.write_f64(self.energy_release_threshold);
+        writer.write_f64(self.energy_store_rate);
+        writer.write_f64(self.energy_release_rate);
+        writer.write_f64(self.material_store_threshold);
+        writer.write_f64(self.material_release_threshold);
+        writer.write_f64(self.material_store_rate);
+        writer.write_f64(self.material_release_rate);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            extra_energy: reader.read_f64()?,
+            extra_material: reader.read_f64()?,
+            energy_store_threshold: reader.read_f64()?,
+            energy_release_threshold: reader.read_f64()?,
+            energy_store_rate: reader.read_f64()?,
+            energy_release_rate: reader.read_f64()?,
+            material_store_threshold: reader.read_f64()?,
+            material_release_threshold: reader.read_f64()?,
+            material_store_rate: reader.read_f64()?,
+            material_release_rate: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for EggCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.stored_energy);
+        writer.write_f64(self.stored_material);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            stored_energy: reader.read_f64()?,
+            stored_material: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for FlagellumCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.phase);
+        writer.write_f64(self.strength);
+        writer.write_f64(self.frequency);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            phase: reader.read_f64()?,
+            strength: reader.read_f64()?,
+            frequency: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for ContractorCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.phase);
+        writer.write_f64(self.contraction);
+        writer.write_f64(self.frequency);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            phase: reader.read_f64()?,
+            contraction: reader.read_f64()?,
+            frequency: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for FoodSensorCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.direction.0);
+        writer.write_f64(self.direction.1);
+        writer.write_f64(self.range);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            direction: (reader.read_f64()?, reader.read_f64()?),
+            range: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for SpikeCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.damage);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            damage: reader.read_f64()?,
+        })
+    }
+}
+
+
+
+impl Snapshot for ArmorCellData {
+    fn write_snapshot (&self, writer: &mut SnapshotWriter) {
+        writer.write_f64(self.damage_reduction);
+    }
+    fn read_snapshot (reader: &mut SnapshotReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            damage_reduction: reader.read_f64()?,
+        })
+    }
+}
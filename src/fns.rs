@@ -1,5 +1,4 @@
 use crate::prelude::*;
-use sdl2::{render::TextureCreator, video::WindowContext, surface::Surface};
 
 
 
@@ -37,12 +36,13 @@ pub fn convert_single_screen_to_grid (screen_pos: i32, camera_pos: f64, zoom: f6
 
 
 pub fn get_entity_ids_near_pos<T: Entity> (grid_pos: (usize, usize), entities: &EntityContainer<T>) -> Vec<EntityID> {
+    if entities.size.wraps() {return get_entity_ids_near_pos_wrapped(grid_pos, entities);}
     let mut output = vec!();
     let (start_x, start_y) = (grid_pos.0.max(1) - 1             , grid_pos.1.max(1) - 1              );
-    let (end_x  , end_y  ) = (grid_pos.0.min(GRID_WIDTH - 2) + 1, grid_pos.1.min(GRID_HEIGHT - 2) + 1);
+    let (end_x  , end_y  ) = (grid_pos.0.min(entities.size.width - 2) + 1, grid_pos.1.min(entities.size.height - 2) + 1);
     for x in start_x..=end_x {
         for y in start_y..=end_y {
-            let slot_ids = &entities.entities_by_pos[x + y * GRID_WIDTH];
+            let slot_ids = &entities.entities_by_pos[x + y * entities.size.width];
             for &id in slot_ids {
                 output.push(id);
             }
@@ -51,27 +51,85 @@ pub fn get_entity_ids_near_pos<T: Entity> (grid_pos: (usize, usize), entities: &
     output
 }
 
+pub fn get_entity_ids_near_pos_wrapped<T: Entity> (grid_pos: (usize, usize), entities: &EntityContainer<T>) -> Vec<EntityID> {
+    let mut output = vec!();
+    let (width, height) = (entities.size.width, entities.size.height);
+    for x in [grid_pos.0 + width - 1, grid_pos.0, grid_pos.0 + 1] {
+        for y in [grid_pos.1 + height - 1, grid_pos.1, grid_pos.1 + 1] {
+            let slot_ids = &entities.entities_by_pos[x % width + y % height * width];
+            for &id in slot_ids {
+                output.push(id);
+            }
+        }
+    }
+    output
+}
 
 
 
+// like get_entity_ids_near_pos(), but gets every slot within `range` slots of the position
+pub fn get_entity_ids_in_range<T: Entity> (grid_pos: (usize, usize), range: usize, entities: &EntityContainer<T>) -> Vec<EntityID> {
+    let mut output = vec!();
+    let (width, height) = (entities.size.width, entities.size.height);
+    if entities.size.wraps() {
+        // the range is limited so that no slot is checked twice
+        let (range_x, range_y) = (range.min((width - 1) / 2), range.min((height - 1) / 2));
+        for x in (grid_pos.0 + width - range_x)..=(grid_pos.0 + width + range_x) {
+            for y in (grid_pos.1 + height - range_y)..=(grid_pos.1 + height + range_y) {
+                output.extend_from_slice(&entities.entities_by_pos[x % width + y % height * width]);
+            }
+        }
+    } else {
+        let (start_x, start_y) = (grid_pos.0.saturating_sub(range), grid_pos.1.saturating_sub(range));
+        let (end_x  , end_y  ) = ((grid_pos.0 + range).min(width - 1), (grid_pos.1 + range).min(height - 1));
+        for x in start_x..=end_x {
+            for y in start_y..=end_y {
+                output.extend_from_slice(&entities.entities_by_pos[x + y * width]);
+            }
+        }
+    }
+    output
+}
 
-pub fn get_program_dir() -> PathBuf {
-    let mut path = std::env::current_exe()
-        .expect("Could not retrieve the path for the current exe.");
-    path.pop();
-    path
+
+
+pub fn wrap_coord (input: f64, size: f64) -> f64 {
+    let output = input.rem_euclid(size);
+    if output >= size {0.} else {output} // rem_euclid can return `size` for tiny negative inputs
+}
+
+pub fn wrap_delta (input: f64, size: f64) -> f64 {
+    let output = input.rem_euclid(size);
+    if output > size / 2. {output - size} else {output}
 }
 
 
 
-pub fn create_texture (width: u32, height: u32, texture_creator: &TextureCreator<WindowContext>) -> Texture {
-    let surface = Surface::new(width, height, sdl2::pixels::PixelFormatEnum::RGBA8888).unwrap();
-    texture_creator.create_texture_from_surface(surface).unwrap()
+
+
+pub fn parse_arg_value<T: std::str::FromStr> (arg: &str, value: Option<String>) -> Result<T, ProgramError> {
+    let Some(value) = value else {
+        return Err(ProgramError::String(format!("Missing value for argument \"{arg}\"")));
+    };
+    value.parse().map_err(|_| ProgramError::String(format!("Invalid value \"{value}\" for argument \"{arg}\"")))
 }
 
-pub fn get_texture_size (texture: &Texture) -> (u32, u32) {
-    let query = texture.query();
-    (query.width, query.height)
+// a zero, negative or non-finite dt would break the simulation, so it's rejected here
+pub fn parse_dt_arg_value (arg: &str, value: Option<String>) -> Result<f64, ProgramError> {
+    let dt: f64 = parse_arg_value(arg, value)?;
+    if !dt.is_finite() || dt <= 0. {
+        return Err(ProgramError::String(format!("Invalid value \"{dt}\" for argument \"{arg}\", expected a number greater than 0")));
+    }
+    Ok(dt)
+}
+
+
+
+pub fn get_program_dir() -> PathBuf {
+    let mut path = std::env::current_exe()
+        .expect("Could not retrieve the path for the current exe.");
+    path.pop();
+    path
 }
 
 
@@ -96,6 +154,17 @@ pub fn find_item_index_custom<T> (input: &[T], test_fn: impl Fn(&T) -> bool) ->
 
 
 
+// finds the root of a union-find set, `parents[i] == i` for roots
+pub fn find_set_root (parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+
+
 pub fn some_if<T> (condition: bool, some_fn: impl FnOnce() -> T) -> Option<T> {
     if condition {
         Some(some_fn())
@@ -145,6 +214,7 @@ pub fn get_many_mut<'a, T> (input: &'a mut [T], indicies: &[usize]) -> Vec<&'a m
 pub fn move_point_to_line (input: (f64, f64), line: (f64, f64)) -> (f64, f64) {
     // input is a_to_p, line is a_to_b, a is assumed to be 0, 0
     let squared_mag = line.0 * line.0 + line.1 * line.1;
+    if squared_mag == 0. {return (0., 0.);} // (cells pushed into the same corner of the walls can end up at the exact same position)
     let input_dot_line = input.0 * line.0 + input.1 * line.1;
     let multiplier = input_dot_line / squared_mag;
     (line.0 * multiplier, line.1 * multiplier)
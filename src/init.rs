@@ -37,7 +37,7 @@ pub fn init_sdl2() -> (Sdl, Canvas<Window>) {
 
 
 
-pub fn init_program_data<'a> (canvas: &Canvas<Window>, texture_creator: &'a TextureCreator<WindowContext>) -> Result<ProgramData<'a>, ProgramError> {
+pub fn init_program_data<'a> (canvas: &Canvas<Window>, texture_creator: &'a TextureCreator<WindowContext>, program_args: &ProgramArgs) -> Result<ProgramData<'a>, ProgramError> {
 
     let textures = load_textures(texture_creator)?;
 
@@ -48,7 +48,28 @@ pub fn init_program_data<'a> (canvas: &Canvas<Window>, texture_creator: &'a Text
 
     let render_data = RenderData::new(textures, font, texture_creator);
 
-    Ok(ProgramData::new(render_data))
+    let settings = SimSettings::load_or_create(&program_args.settings_path)?;
+    let (world, camera) = match &program_args.load_path {
+        Some(load_path) => load_world(load_path, settings)?,
+        None => {
+            let mut world_size = program_args.world_size;
+            if let Some(max_entities_count) = program_args.max_entities_count {world_size.max_entities_count = max_entities_count;}
+            if program_args.wrap {world_size.boundary_mode = BoundaryMode::Wrap;}
+            let mut world = World::new(world_size, settings, program_args.seed);
+            world.add_test_data();
+            (world, Camera::new())
+        }
+    };
+    let fixed_timestep = program_args.fixed_dt.map(FixedTimestep::new);
+    let settings_watcher = SettingsWatcher::new(program_args.settings_path.clone());
+
+    let mut program_data = ProgramData::new(render_data, world, camera, fixed_timestep, settings_watcher);
+    if program_args.profile_frame_count.is_some() {
+        program_data.profile_frame_count = program_args.profile_frame_count;
+        program_data.world.profile_data = Some(ProfileData::new());
+    }
+
+    Ok(program_data)
 }
 
 
@@ -0,0 +1,18 @@
+// default rust
+#![allow(unused)]
+#![warn(unused_must_use)]
+
+
+
+// General Settings
+
+pub const DEFAULT_GRID_WIDTH: usize = 128;
+pub const DEFAULT_GRID_HEIGHT: usize = 128;
+pub const MIN_GRID_SIZE: usize = 4;
+pub const FIXED_TIMESTEP_MAX_CATCHUP: f64 = 0.25;
+
+pub mod update_mod;
+pub mod data_mod;
+pub mod fns;
+pub mod logger;
+pub mod prelude;
@@ -17,7 +17,7 @@ impl Logger {
 
     pub fn log (&mut self, input: &[u8]) {
         let _ = self.output.write_all(input);
-        let _ = self.output.write(&[b'\n']);
+        let _ = self.output.write(b"\n");
     }
 
     pub fn flush (&mut self) -> Result<(), std::io::Error> {
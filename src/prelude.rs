@@ -1,17 +1,15 @@
-pub use crate::{*, update_mod::{*, update_data::*}, render_mod::*, logger::*,
-    data_mod::{general_data::*, cell_data::*, entity_container::*, errors::*},
+pub use crate::{*, update_mod::{*, update_data::*}, logger::*,
+    data_mod::{general_data::*, cell_data::*, connection_data::*, organism_data::*, genome::*, controller::*, entity_container::*, id_arena::*, errors::*, rng::*, snapshot::*, settings::*},
 };
 
 pub use std::{fmt, fs,
     io::{Error as IoError, ErrorKind as IoErrorKind},
     thread::{self, JoinHandle},
     path::{PathBuf, Path},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
     sync::{Arc, Mutex, MutexGuard},
 };
 
-pub use sdl2::{render::Texture, rect::Rect, pixels::Color};
-pub use ab_glyph::*;
 pub use rayon::prelude::*;
 pub use hashbrown::*;
 pub use array_init::array_init;
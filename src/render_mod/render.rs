@@ -8,6 +8,7 @@ use sdl2::{render::WindowCanvas};
 pub fn render(canvas: &mut WindowCanvas, program_data: &mut ProgramData) -> Result<(), ProgramError> {
     let canvas_size = canvas.output_size()?;
     let camera = &program_data.camera;
+    let world_size = program_data.world.size;
 
     //canvas.set_draw_color(Color::RGB(255, 0, 255));
     //canvas.clear();
@@ -30,7 +31,8 @@ pub fn render(canvas: &mut WindowCanvas, program_data: &mut ProgramData) -> Resu
             let mut next_screen_x = start_next_x;
             'x: loop {
                 let dst = Rect::new(curr_screen_x, curr_screen_y, (next_screen_x - curr_screen_x) as u32, (next_screen_y - curr_screen_y) as u32);
-                let texture = if curr_grid_x < 0 || curr_grid_y < 0 || curr_grid_x >= GRID_WIDTH as isize || curr_grid_y >= GRID_HEIGHT as isize {
+                let is_outside_world = curr_grid_x < 0 || curr_grid_y < 0 || curr_grid_x >= world_size.width as isize || curr_grid_y >= world_size.height as isize;
+                let texture = if is_outside_world && !world_size.wraps() {
                     &textures.black_ground
                 } else {
                     &textures.ground
@@ -53,17 +55,34 @@ pub fn render(canvas: &mut WindowCanvas, program_data: &mut ProgramData) -> Resu
     }
     //println!("{}", start_instant.elapsed().as_micros());
 
-    { // draw entities
+    if world_size.wraps() { // draw entities (wrapped)
+        let textures = &program_data.render_data.textures;
+        let (width, height) = (world_size.width as isize, world_size.height as isize);
+        for y in (start_grid_y - 1)..=(end_grid_y + 1) {
+            for x in (start_grid_x - 1)..=(end_grid_x + 1) {
+                // entities in wrapped copies of the world are drawn by shifting the camera by the copy's offset
+                let (slot_x, slot_y) = (x.rem_euclid(width), y.rem_euclid(height));
+                let tile_camera = Camera {
+                    x: camera.x - (x - slot_x) as f64,
+                    y: camera.y - (y - slot_y) as f64,
+                    zoom: camera.zoom,
+                };
+                let (slot_x, slot_y) = (slot_x as usize, slot_y as usize);
+                draw_entities(slot_x, slot_y, &program_data.world.cells, &tile_camera, canvas, canvas_size, textures)?;
+                draw_entities(slot_x, slot_y, &program_data.world.food, &tile_camera, canvas, canvas_size, textures)?;
+            }
+        }
+    } else { // draw entities
         let textures = &program_data.render_data.textures;
         let start_grid_x = (start_grid_x - 1).max(0);
         let start_grid_y = (start_grid_y - 1).max(0);
-        let end_grid_x = (end_grid_x + 1).min(GRID_WIDTH  as isize - 1);
-        let end_grid_y = (end_grid_y + 1).min(GRID_HEIGHT as isize - 1);
+        let end_grid_x = (end_grid_x + 1).min(world_size.width  as isize - 1);
+        let end_grid_y = (end_grid_y + 1).min(world_size.height as isize - 1);
         for y in start_grid_y..=end_grid_y {
             for x in start_grid_x..=end_grid_x {
                 let (x, y) = (x as usize, y as usize);
-                draw_entities(x, y, &program_data.cells, camera, canvas, canvas_size, textures)?;
-                draw_entities(x, y, &program_data.food, camera, canvas, canvas_size, textures)?;
+                draw_entities(x, y, &program_data.world.cells, camera, canvas, canvas_size, textures)?;
+                draw_entities(x, y, &program_data.world.food, camera, canvas, canvas_size, textures)?;
             }
         }
     }
@@ -71,13 +90,23 @@ pub fn render(canvas: &mut WindowCanvas, program_data: &mut ProgramData) -> Resu
     // draw selected entity information
     match program_data.selected_entity {
         EntitySelection::None => {}
-        EntitySelection::Cell (entity_id) => draw_cell_information(entity_id, program_data, canvas, canvas_size)?,
+        EntitySelection::Cell (entity_id) => {
+            draw_organism_outline(entity_id, program_data, canvas, canvas_size)?;
+            draw_cell_information(entity_id, program_data, canvas, canvas_size)?;
+        }
         EntitySelection::Food (entity_id) => {}
     }
 
+    // draw sim status
+    draw_sim_status(program_data, canvas, canvas_size)?;
+
+    // draw settings errors
+    if let Some(error) = program_data.settings_watcher.error.clone() {
+        draw_settings_error(&error, program_data, canvas, canvas_size)?;
+    }
+
     // finish
     canvas.present();
-    program_data.frame_count += 1;
 
     Ok(())
 }
@@ -86,8 +115,8 @@ pub fn render(canvas: &mut WindowCanvas, program_data: &mut ProgramData) -> Resu
 
 
 
-pub fn draw_entities<T: Entity + AsRef<RawEntity>> (x: usize, y: usize, entities_container: &EntityContainer<T>, camera: &Camera, canvas: &mut WindowCanvas, canvas_size: (u32, u32), textures: &ProgramTextures) -> Result<(), ProgramError> {
-    let current_slot = &entities_container.entities_by_pos[x + y * GRID_WIDTH];
+pub fn draw_entities<T: Entity + EntityTexture + AsRef<RawEntity>> (x: usize, y: usize, entities_container: &EntityContainer<T>, camera: &Camera, canvas: &mut WindowCanvas, canvas_size: (u32, u32), textures: &ProgramTextures) -> Result<(), ProgramError> {
+    let current_slot = &entities_container.entities_by_pos[x + y * entities_container.size.width];
     for cell_id in current_slot {
         let entity = entities_container.get(*cell_id).unwrap();
         let raw_entity = entity.as_ref();
@@ -109,7 +138,7 @@ pub fn get_entity_rect (entity: &RawEntity, camera: &Camera, canvas_size: (u32,
 
 
 pub fn draw_cell_information (cell_id: EntityID, program_data: &mut ProgramData, canvas: &mut WindowCanvas, canvas_size: (u32, u32)) -> Result<(), ProgramError> {
-    let cell = program_data.cells.get(cell_id).unwrap();
+    let Some(cell) = program_data.world.cells.get(cell_id) else {return Ok(());};
     let main_area = Area::new(canvas_size);
     let menu_area = main_area.get_sub_area(0.0, 0.02, 0., 0.96, 0.02, 0.43);
     render_fns::draw_menu_background(menu_area.to_rect(), canvas)?;
@@ -133,12 +162,113 @@ pub fn draw_cell_information (cell_id: EntityID, program_data: &mut ProgramData,
     let text_pos = menu_area.get_point(0.05, 0.19, 0.0);
     render_fns::draw_text("Material: ".to_string() + &material.to_string(), text_pos, 0.0, canvas_size.1 / 25, canvas, &mut program_data.render_data)?;
 
-    let cell_data_area = menu_area.get_sub_area(0.05, 0.28, 0.9, 0.70, 0., 0.);
+    // "Generation: "
+    let generation = cell.genome.as_ref().map_or(0, |genome| genome.generation);
+    let text_pos = menu_area.get_point(0.05, 0.24, 0.0);
+    render_fns::draw_text("Generation: ".to_string() + &generation.to_string(), text_pos, 0.0, canvas_size.1 / 25, canvas, &mut program_data.render_data)?;
+
+    // "Organism: "
+    let organism = cell.organism_id.and_then(|organism_id| program_data.world.organisms.get(organism_id));
+    let organism_text = match organism {
+        Some(organism) => format!("Organism: {} cells, {}s old", organism.cell_ids.len(), (organism.age * 10.).round() / 10.),
+        None => String::from("Organism: none"),
+    };
+    let text_pos = menu_area.get_point(0.05, 0.29, 0.0);
+    render_fns::draw_text(organism_text, text_pos, 0.0, canvas_size.1 / 25, canvas, &mut program_data.render_data)?;
+
+    // "Activity: "
+    let activity = (cell.get_activity() * 100.).round() / 100.;
+    let reproduce = (cell.controller_outputs[CONTROLLER_OUTPUT_REPRODUCE] * 100.).round() / 100.;
+    let text_pos = menu_area.get_point(0.05, 0.34, 0.0);
+    render_fns::draw_text(format!("Activity: {activity}, reproduce: {reproduce}"), text_pos, 0.0, canvas_size.1 / 25, canvas, &mut program_data.render_data)?;
+
+    // "Signals: "
+    let signals = cell.signals.iter().map(|signal| ((signal * 100.).round() / 100.).to_string()).collect::<Vec<String>>();
+    let text_pos = menu_area.get_point(0.05, 0.39, 0.0);
+    render_fns::draw_text("Signals: ".to_string() + &signals.join(", "), text_pos, 0.0, canvas_size.1 / 25, canvas, &mut program_data.render_data)?;
+
+    let cell_data_area = menu_area.get_sub_area(0.05, 0.45, 0.9, 0.53, 0., 0.);
     render_fns::draw_menu_background(cell_data_area.to_rect(), canvas)?;
 
     match &cell.raw_cell {
         RawCell::Fat (fat_cell_data) => draw_cell_information_fat(fat_cell_data, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
         RawCell::Photosynthesiser => draw_cell_information_photosythesiser(cell_data_area, program_data, canvas, canvas_size)?,
+        RawCell::Egg (egg_cell_data) => draw_cell_information_egg(egg_cell_data, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::Mouth => draw_cell_information_mouth(cell_data_area, program_data, canvas, canvas_size)?,
+        RawCell::Flagellum (flagellum_cell_data) => draw_cell_information_flagellum(flagellum_cell_data, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::Contractor (contractor_cell_data) => draw_cell_information_contractor(contractor_cell_data, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::Adhesive => draw_cell_information_adhesive(cell_data_area, program_data, canvas, canvas_size)?,
+        RawCell::FoodSensor (food_sensor_cell_data) => draw_cell_information_food_sensor(food_sensor_cell_data, cell.sensor_value, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::LightSensor => draw_cell_information_light_sensor(cell.sensor_value, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::ContactSensor => draw_cell_information_contact_sensor(cell.sensor_value, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::Spike (spike_cell_data) => draw_cell_information_spike(spike_cell_data, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+        RawCell::Armor (armor_cell_data) => draw_cell_information_armor(armor_cell_data, &program_data.world.settings, cell_data_area, canvas, canvas_size, &mut program_data.render_data)?,
+    }
+
+    Ok(())
+}
+
+
+
+
+
+// draws the bounding box of the selected cell's organism
+pub fn draw_organism_outline (cell_id: EntityID, program_data: &ProgramData, canvas: &mut WindowCanvas, canvas_size: (u32, u32)) -> Result<(), ProgramError> {
+    let Some(cell) = program_data.world.cells.get(cell_id) else {return Ok(());};
+    let Some(organism) = cell.organism_id.and_then(|organism_id| program_data.world.organisms.get(organism_id)) else {return Ok(());};
+    let (min, max) = organism.bounding_box;
+    let (top_left_x    , top_left_y    ) = fns::convert_grid_to_screen((min.0 - 0.5, min.1 - 0.5), &program_data.camera, canvas_size);
+    let (bottom_right_x, bottom_right_y) = fns::convert_grid_to_screen((max.0 + 0.5, max.1 + 0.5), &program_data.camera, canvas_size);
+    canvas.set_draw_color(Color::RGB(255, 255, 255));
+    canvas.draw_rect(Rect::new(top_left_x, top_left_y, (bottom_right_x - top_left_x) as u32, (bottom_right_y - top_left_y) as u32))?;
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_sim_status (program_data: &mut ProgramData, canvas: &mut WindowCanvas, canvas_size: (u32, u32)) -> Result<(), ProgramError> {
+    let main_area = Area::new(canvas_size);
+    let text_pos = main_area.get_point(0.99, 0.01, 0.0);
+    let status = if program_data.paused {
+        String::from("Paused (press . to step)")
+    } else {
+        format!("Speed: {}x", program_data.time_scale)
+    };
+    let status = format!("{status}   Frame: {}", program_data.world.frame_count);
+    render_fns::draw_text(status, text_pos, 1.0, canvas_size.1 / 35, canvas, &mut program_data.render_data)?;
+
+    // status message (reloads, saves & loads)
+    if let Some((message, message_instant)) = &program_data.status_message {
+        if message_instant.elapsed().as_secs_f64() < STATUS_MESSAGE_DURATION {
+            let text_pos = main_area.get_point(0.99, 0.05, 0.0);
+            render_fns::draw_text(message.clone(), text_pos, 1.0, canvas_size.1 / 40, canvas, &mut program_data.render_data)?;
+        }
+    }
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_settings_error (error: &str, program_data: &mut ProgramData, canvas: &mut WindowCanvas, canvas_size: (u32, u32)) -> Result<(), ProgramError> {
+    let lines = error.lines().collect::<Vec<&str>>();
+    let main_area = Area::new(canvas_size);
+    let height = 0.07 + lines.len() as f64 * 0.035;
+    let menu_area = main_area.get_basic_sub_area(0.5, 0.98 - height, 0.49, height);
+    render_fns::draw_menu_background(menu_area.to_rect(), canvas)?;
+
+    // "Invalid settings"
+    let text_pos = menu_area.get_point(0.02, 0.01, 0.0);
+    render_fns::draw_text("Invalid settings (still using the last valid settings):", text_pos, 0.0, canvas_size.1 / 35, canvas, &mut program_data.render_data)?;
+
+    // errors
+    for (i, line) in lines.iter().enumerate() {
+        let text_pos = main_area.get_point(0.51, 0.98 - height + 0.045 + i as f64 * 0.035, 0.0);
+        render_fns::draw_text(line, text_pos, 0.0, canvas_size.1 / 40, canvas, &mut program_data.render_data)?;
     }
 
     Ok(())
@@ -175,3 +305,221 @@ pub fn draw_cell_information_photosythesiser (cell_data_area: Area, program_data
 
     Ok(())
 }
+
+
+
+
+
+
+pub fn draw_cell_information_egg (cell_data: &EggCellData, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Egg Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Egg Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Stored energy: "
+    let stored_energy = (cell_data.stored_energy * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Stored energy: ".to_string() + &stored_energy.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Stored material: "
+    let stored_material = (cell_data.stored_material * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.18, 0.0);
+    render_fns::draw_text("Stored material: ".to_string() + &stored_material.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+
+pub fn draw_cell_information_mouth (cell_data_area: Area, program_data: &mut ProgramData, canvas: &mut WindowCanvas, canvas_size: (u32, u32)) -> Result<(), ProgramError> {
+
+    // "Mouth Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Mouth Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, &mut program_data.render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+
+pub fn draw_cell_information_flagellum (cell_data: &FlagellumCellData, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Flagellum Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Flagellum Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Strength: "
+    let strength = (cell_data.strength * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Strength: ".to_string() + &strength.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Frequency: "
+    let frequency = (cell_data.frequency * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.18, 0.0);
+    render_fns::draw_text("Frequency: ".to_string() + &frequency.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Phase: "
+    let phase = (cell_data.phase * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.24, 0.0);
+    render_fns::draw_text("Phase: ".to_string() + &phase.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+
+pub fn draw_cell_information_contractor (cell_data: &ContractorCellData, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Contractor Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Contractor Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Max contraction: "
+    let contraction = (cell_data.contraction * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Max contraction: ".to_string() + &contraction.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Frequency: "
+    let frequency = (cell_data.frequency * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.18, 0.0);
+    render_fns::draw_text("Frequency: ".to_string() + &frequency.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Current contraction: "
+    let current_contraction = (cell_data.get_contraction() * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.24, 0.0);
+    render_fns::draw_text("Current contraction: ".to_string() + &current_contraction.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_cell_information_adhesive (cell_data_area: Area, program_data: &mut ProgramData, canvas: &mut WindowCanvas, canvas_size: (u32, u32)) -> Result<(), ProgramError> {
+
+    // "Adhesive Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Adhesive Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, &mut program_data.render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_cell_information_food_sensor (cell_data: &FoodSensorCellData, sensor_value: f64, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Food Sensor Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Food Sensor Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Sensor value: "
+    let sensor_value = (sensor_value * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Sensor value: ".to_string() + &sensor_value.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Direction: "
+    let direction = ((cell_data.direction.0 * 100.).round() / 100., (cell_data.direction.1 * 100.).round() / 100.);
+    let text_pos = cell_data_area.get_point(0.05, 0.18, 0.0);
+    render_fns::draw_text(format!("Direction: {}, {}", direction.0, direction.1), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Range: "
+    let range = (cell_data.range * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.24, 0.0);
+    render_fns::draw_text("Range: ".to_string() + &range.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_cell_information_light_sensor (sensor_value: f64, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Light Sensor Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Light Sensor Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Light level: "
+    let light_level = (sensor_value * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Light level: ".to_string() + &light_level.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_cell_information_contact_sensor (sensor_value: f64, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Contact Sensor Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Contact Sensor Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Touching foreign cells: "
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Touching foreign cells: ".to_string() + &sensor_value.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_cell_information_spike (cell_data: &SpikeCellData, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Spike Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Spike Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Damage: "
+    let damage = (cell_data.damage * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Damage: ".to_string() + &damage.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
+
+
+
+
+
+pub fn draw_cell_information_armor (cell_data: &ArmorCellData, settings: &SimSettings, cell_data_area: Area, canvas: &mut WindowCanvas, canvas_size: (u32, u32), render_data: &mut RenderData) -> Result<(), ProgramError> {
+
+    // "Armor Cell"
+    let text_pos = cell_data_area.get_point(0.5, 0.02, 0.0);
+    render_fns::draw_text("Armor Cell", text_pos, 0.5, canvas_size.1 / 20, canvas, render_data)?;
+
+    // "Damage reduction: "
+    let damage_reduction = (cell_data.damage_reduction * 100.).round() / 100.;
+    let text_pos = cell_data_area.get_point(0.05, 0.12, 0.0);
+    render_fns::draw_text("Damage reduction: ".to_string() + &damage_reduction.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Energy upkeep: "
+    let energy_upkeep = (cell_data.damage_reduction * settings.armor_energy_upkeep * 1000.).round() / 1000.;
+    let text_pos = cell_data_area.get_point(0.05, 0.18, 0.0);
+    render_fns::draw_text("Energy upkeep: ".to_string() + &energy_upkeep.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    // "Material upkeep: "
+    let material_upkeep = (cell_data.damage_reduction * settings.armor_material_upkeep * 1000.).round() / 1000.;
+    let text_pos = cell_data_area.get_point(0.05, 0.24, 0.0);
+    render_fns::draw_text("Material upkeep: ".to_string() + &material_upkeep.to_string(), text_pos, 0.0, canvas_size.1 / 30, canvas, render_data)?;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use sdl2::{render::WindowCanvas};
+use sdl2::{render::{WindowCanvas, TextureCreator}, video::WindowContext, surface::Surface};
 
 
 
@@ -57,7 +57,7 @@ pub fn draw_text (text: impl AsRef<str>, text_pos: (i32, i32), x_align: f64, siz
     // place text
     for (i, glyph) in glyphs.iter().enumerate() {
         let texture = render_data.glyph_cache.get(&HashableGlyph::from_glyph(glyph)).unwrap();
-        let texture_size = fns::get_texture_size(&texture.texture);
+        let texture_size = render_fns::get_texture_size(&texture.texture);
         let dst = Rect::new(left_x + glyph_positions[i] as i32 + texture.origin_x, text_pos.1 + texture.origin_y + size as i32, texture_size.0, texture_size.1);
         canvas.copy(&texture.texture, None, dst)?;
     }
@@ -91,7 +91,7 @@ pub fn render_glyph<'a> (glyph: &Glyph, render_data: &mut RenderData<'a>) -> Res
     let glyph_outline = match render_data.font.outline_glyph(glyph.clone()) {
         Some(v) => v,
         None => {
-            let texture = fns::create_texture(glyph.scale.x as u32, glyph.scale.y as u32, render_data.texture_creator);
+            let texture = render_fns::create_texture(glyph.scale.x as u32, glyph.scale.y as u32, render_data.texture_creator);
             return Ok(GlyphTexture {texture, origin_x: 0, origin_y: 0});
         },
     };
@@ -109,7 +109,7 @@ pub fn render_glyph<'a> (glyph: &Glyph, render_data: &mut RenderData<'a>) -> Res
     });
 
     // vec -> texture
-    let mut texture = fns::create_texture(width as u32, height as u32, render_data.texture_creator);
+    let mut texture = render_fns::create_texture(width as u32, height as u32, render_data.texture_creator);
     texture.update(None, &pixel_data, width * 4)?;
 
     Ok(GlyphTexture {
@@ -143,3 +143,17 @@ pub fn clamp_to_section (rect: &Rect, section: &Rect) -> (Rect, Rect) {
     let dest = Rect::new(shown_lx + section_lx, shown_ly + section_ly, (shown_hx - shown_lx) as u32, (shown_hy - shown_ly) as u32);
     (src, dest)
 }
+
+
+
+
+
+pub fn create_texture (width: u32, height: u32, texture_creator: &TextureCreator<WindowContext>) -> Texture<'_> {
+    let surface = Surface::new(width, height, sdl2::pixels::PixelFormatEnum::RGBA8888).unwrap();
+    texture_creator.create_texture_from_surface(surface).unwrap()
+}
+
+pub fn get_texture_size (texture: &Texture) -> (u32, u32) {
+    let query = texture.query();
+    (query.width, query.height)
+}
@@ -1,3 +1,2 @@
 pub mod update;
 pub mod update_data;
-pub mod events;
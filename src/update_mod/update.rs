@@ -1,117 +1,84 @@
 use crate::prelude::*;
-use sdl2::{keyboard::Keycode, render::WindowCanvas};
 
 
 
-static mut TOTAL_TIME: f64 = 0.0;
-
-pub fn update (program_data: &mut ProgramData, canvas: &WindowCanvas, events_data: EventsData, dt: f64) -> Result<(), ProgramError> {
-    
-    events::process_events(program_data, events_data, canvas)?;
-
-    move_camera(program_data, dt);
-
-    let start = Instant::now();
-    update_cells(program_data, dt);
-    //println!("total: update time (ms): {}\n\n\n", start.elapsed().as_secs_f64() * 1000.);
-
-    unsafe {
-        TOTAL_TIME += start.elapsed().as_secs_f64();
-    }
-    if program_data.frame_count == 300 {
-        unsafe {
-            println!("{}", TOTAL_TIME);
-        }
-        panic!("intentional stop to analyize data");
-    }
-
-    Ok(())
-}
-
-
-
-pub fn move_camera(program_data: &mut ProgramData, dt: f64) {
-    let current_speed = CAMERA_SPEED / program_data.camera.zoom * dt;
-
-    if program_data.key_is_pressed(Keycode::W) {
-        program_data.camera.y -= current_speed;
-    }
-    if program_data.key_is_pressed(Keycode::S) {
-        program_data.camera.y += current_speed;
-    }
-
-    if program_data.key_is_pressed(Keycode::A) {
-        program_data.camera.x -= current_speed;
-    }
-    if program_data.key_is_pressed(Keycode::D) {
-        program_data.camera.x += current_speed;
-    }
-
-}
-
-
-
-pub fn update_cells(program_data: &mut ProgramData, dt: f64) {
-    if program_data.frame_count < 30 {return;}
+pub fn update_cells (world: &mut World, dt: f64) {
+    if world.frame_count < world.settings.warmup_frames {return;}
+    let mut phase_instant = Instant::now();
 
     // remove invalid ids
-    for i in 0..program_data.cells.master_list.len() {
-        let cell_data = &program_data.cells.master_list[i];
-        if cell_data.0.is_none() {continue;}
-        let curr_cell_id = (i, cell_data.1);
-        remove_invalid_ids(curr_cell_id, &mut program_data.cells);
-    }
-
-    let cells = &program_data.cells;
+    remove_invalid_ids(world);
+
+    let context = UpdateContext {
+        cells: &world.cells,
+        food: &world.food,
+        connections: &world.connections,
+        organisms: &world.organisms,
+        settings: &world.settings,
+        dt,
+    };
+    let (seed, frame_count) = (world.seed, world.frame_count);
 
     // main update
-    //let start = Instant::now();
-    let all_updates: Vec<WorldUpdates> = program_data.cells.master_list.par_iter().enumerate().map(|(i, cell_data)| {
+    let all_updates: Vec<WorldUpdates> = world.cells.master_list.par_iter().enumerate().map(|(i, cell_data)| {
         let mut world_updates = WorldUpdates::new();
         if cell_data.0.is_none() {return world_updates;}
         let curr_cell_id = (i, cell_data.1);
 
         let mut cell_changes_group = CellChangesGroup::new();
-        let update_result = update_single_cell(curr_cell_id, cells, &mut world_updates, &mut cell_changes_group, dt);
+        let mut rng = SimRng::new_for_entity(seed, frame_count, i);
+        let update_result = update_single_cell(curr_cell_id, &context, &mut world_updates, &mut cell_changes_group);
+        if update_result == CellUpdateResult::Removed {return world_updates;}
+        update_controller(curr_cell_id, &context, &mut world_updates);
+        // a hatching egg gives everything it has to the offspring, so it can't also transfer anything to its connected cells
+        let update_result = update_cell_by_type(curr_cell_id, &context, &mut world_updates, &mut cell_changes_group, &mut rng);
         if update_result == CellUpdateResult::Removed {return world_updates;}
-        update_cell_by_type(curr_cell_id, cells, &mut world_updates, &mut cell_changes_group, dt);
-        update_connected_cells(curr_cell_id, cells, &mut world_updates, &mut cell_changes_group, dt);
-        update_nearby_cells(curr_cell_id, cells, &mut world_updates, &mut cell_changes_group, dt);
+        update_reproduction(curr_cell_id, &context, &mut world_updates, &mut cell_changes_group, &mut rng);
+        update_connected_cells(curr_cell_id, &context, &mut world_updates, &mut cell_changes_group);
+        update_nearby_cells(curr_cell_id, &context, &mut world_updates, &mut cell_changes_group);
 
         cell_changes_group.add_self_to_world_updates(&mut world_updates, curr_cell_id);
 
         world_updates
     }).collect();
-    //println!("main update time (ms): {}", start.elapsed().as_secs_f64() * 1000.);
-    
-    let (mut all_changes, mut all_additions) = (vec!(), vec!());
+    world.record_phase_time(ProfilePhase::MainPass, &mut phase_instant);
+
+    // NOTE: collect() keeps the updates in cell order, so changes are always applied in the same order no matter how many threads are used
+    let (mut all_changes, mut all_additions): (Vec<_>, Vec<_>) = (vec!(), vec!());
     for world_updates in all_updates {
         all_changes.push(world_updates.changes);
         all_additions.push(world_updates.additions);
     }
 
     // apply change updates
-    //let start = Instant::now();
-    //let mut size = 0;
     for changes in all_changes {
-        //size += changes.len();
         for change in changes {
-            apply_change_update(change, program_data);
+            apply_change_update(change, world);
         }
     }
-    //println!("{size}");
+    collect_hatched_egg_resources(&mut all_additions, world);
+    world.record_phase_time(ProfilePhase::ApplyChanges, &mut phase_instant);
 
     // sync feilds (& remove entities)
-    program_data.cells.sync_feilds();
-    program_data.food.sync_feilds();
+    world.cells.sync_feilds();
+    world.food.sync_feilds();
+    world.record_phase_time(ProfilePhase::SyncFeilds, &mut phase_instant);
 
     // apply addition updates
     for additions in all_additions {
         for addition in additions {
-            apply_addition_update(addition, program_data);
+            apply_addition_update(addition, world);
         }
     }
-    //println!("apply update time (ms): {}", start.elapsed().as_secs_f64() * 1000.);
+    world.record_phase_time(ProfilePhase::ApplyAdditions, &mut phase_instant);
+
+    // update organisms
+    update_organisms(world, dt);
+    world.record_phase_time(ProfilePhase::Organisms, &mut phase_instant);
+
+    if let Some(profile_data) = &mut world.profile_data {
+        profile_data.step_count += 1;
+    }
 
 }
 
@@ -119,17 +86,17 @@ pub fn update_cells(program_data: &mut ProgramData, dt: f64) {
 
 
 
-pub fn remove_invalid_ids (curr_cell_id: EntityID, cells: &mut EntityContainer<Cell>) {
-    let current_cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
-    let mut id_indexes_to_remove = vec!();
-    for (i, connected_cell_id) in current_cell.connected_cells.iter().enumerate().rev() {
-        if !cells.id_is_valid(*connected_cell_id) {
-            id_indexes_to_remove.push(i);
+// removes every connection that has a dead cell on either end
+pub fn remove_invalid_ids (world: &mut World) {
+    let mut connection_ids_to_remove = vec!();
+    for (i, connection_data) in world.connections.list.iter().enumerate() {
+        let Some(connection) = &connection_data.0 else {continue;};
+        if !world.cells.id_is_valid(connection.cell_ids.0) || !world.cells.id_is_valid(connection.cell_ids.1) {
+            connection_ids_to_remove.push((i, connection_data.1));
         }
     }
-    let current_cell = cells.master_list[curr_cell_id.0].0.as_mut().unwrap();
-    for id_to_remove in id_indexes_to_remove {
-        current_cell.connected_cells.remove(id_to_remove);
+    for connection_id in connection_ids_to_remove {
+        world.disconnect_cells(connection_id);
     }
 }
 
@@ -137,65 +104,260 @@ pub fn remove_invalid_ids (curr_cell_id: EntityID, cells: &mut EntityContainer<C
 
 
 
-pub fn apply_change_update (update: ChangeUpdate, program_data: &mut ProgramData) {
+// finds every group of connected cells and matches it to the organism most of its cells belonged to
+// when an organism splits, the biggest piece keeps its id, and when organisms merge, the one with the most cells in the group keeps its id
+pub fn update_organisms (world: &mut World, dt: f64) {
+
+    // group connected cells
+    let mut parents = (0..world.cells.master_list.len()).collect::<Vec<usize>>();
+    for connection in world.connections.iter() {
+        let (cell_id_1, cell_id_2) = connection.cell_ids;
+        if !world.cells.id_is_valid(cell_id_1) || !world.cells.id_is_valid(cell_id_2) {continue;}
+        let root_1 = fns::find_set_root(&mut parents, cell_id_1.0);
+        let root_2 = fns::find_set_root(&mut parents, cell_id_2.0);
+        parents[root_1.max(root_2)] = root_1.min(root_2);
+    }
+    let mut groups: Vec<Vec<EntityID>> = vec!();
+    let mut group_indices = HashMap::new(); // set root -> index into groups
+    for (i, cell_data) in world.cells.master_list.iter().enumerate() {
+        if cell_data.0.is_none() {continue;}
+        let root = fns::find_set_root(&mut parents, i);
+        let group_index = *group_indices.entry(root).or_insert_with(|| {
+            groups.push(vec!());
+            groups.len() - 1
+        });
+        groups[group_index].push((i, cell_data.1));
+    }
+
+    // find which organism each group continues (and how many of its cells that organism had)
+    let mut previous_organisms = Vec::with_capacity(groups.len());
+    let mut best_groups: HashMap<OrganismID, (usize, usize)> = HashMap::new(); // organism id -> index & cell count of the group with the most of its cells
+    for (group_index, group) in groups.iter().enumerate() {
+        let mut counts: Vec<(OrganismID, usize)> = vec!();
+        for &cell_id in group {
+            let Some(organism_id) = world.cells.get(cell_id).unwrap().organism_id else {continue;};
+            if world.organisms.get(organism_id).is_none() {continue;}
+            match counts.iter_mut().find(|(id, _)| *id == organism_id) {
+                Some(count) => count.1 += 1,
+                None => counts.push((organism_id, 1)),
+            }
+        }
+        if counts.len() > 1 {world.organisms.merge_count += counts.len() as u64 - 1;}
+        let previous_organism = counts.into_iter().fold(None, |best: Option<(OrganismID, usize)>, count| {
+            if best.is_none_or(|best| count.1 > best.1) {Some(count)} else {best}
+        });
+        if let Some((organism_id, count)) = previous_organism {
+            let best_group = best_groups.entry(organism_id).or_insert((group_index, count));
+            if count > best_group.1 {*best_group = (group_index, count);}
+        }
+        previous_organisms.push(previous_organism);
+    }
+
+    // remove organisms that died or were merged into another one
+    for organism_id in world.organisms.ids() {
+        if !best_groups.contains_key(&organism_id) {
+            world.organisms.remove(organism_id);
+        }
+    }
+
+    // update organisms & add new ones
+    for (group_index, group) in groups.into_iter().enumerate() {
+        let organism_id = match previous_organisms[group_index] {
+            Some((organism_id, _)) if best_groups[&organism_id].0 == group_index => {
+                world.organisms.get_mut(organism_id).unwrap().age += dt;
+                organism_id
+            }
+            Some((parent_id, _)) => {
+                world.organisms.split_count += 1;
+                world.organisms.add(Organism::new(Some(parent_id)))
+            }
+            None => world.organisms.add(Organism::new(None)),
+        };
+        for &cell_id in &group {
+            world.cells.get_mut(cell_id).unwrap().organism_id = Some(organism_id);
+        }
+        world.organisms.get_mut(organism_id).unwrap().set_cells(group, &world.cells);
+    }
+
+}
+
+
+
+
+
+pub fn apply_change_update (update: ChangeUpdate, world: &mut World) {
     match update {
 
         ChangeUpdate::ChangeCellHealth (cell_index, value) => {
-            program_data.cells.master_list[cell_index].0.as_mut().unwrap().health += value;
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            // armor only reduces damage, not healing
+            match &cell.raw_cell {
+                RawCell::Armor (armor_cell_data) if value < 0. => cell.health += value * (1. - armor_cell_data.damage_reduction),
+                _ => cell.health += value,
+            }
         }
 
         ChangeUpdate::ChangeCellEnergy (cell_index, value) => {
-            program_data.cells.master_list[cell_index].0.as_mut().unwrap().energy += value;
+            world.cells.master_list[cell_index].0.as_mut().unwrap().energy += value;
         }
 
         ChangeUpdate::ChangeCellMaterial (cell_index, value) => {
-            program_data.cells.master_list[cell_index].0.as_mut().unwrap().material += value;
+            world.cells.master_list[cell_index].0.as_mut().unwrap().material += value;
         }
 
         ChangeUpdate::SetCellPos (cell_index, value_1, value_2) => {
-            let cell = program_data.cells.master_list[cell_index].0.as_mut().unwrap();
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
             cell.entity.x = value_1;
             cell.entity.y = value_2;
         }
 
         ChangeUpdate::ChangeCellVel (cell_index, value_1, value_2) => {
-            let cell = program_data.cells.master_list[cell_index].0.as_mut().unwrap();
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
             cell.x_vel += value_1;
             cell.y_vel += value_2;
         }
 
         ChangeUpdate::SetCellIsActive (cell_index, value) => {
-            program_data.cells.master_list[cell_index].0.as_mut().unwrap().is_active = value;
+            world.cells.master_list[cell_index].0.as_mut().unwrap().is_active = value;
         }
 
         ChangeUpdate::SetCellShouldBeRemoved (cell_index, value) => {
-            program_data.cells.master_list[cell_index].0.as_mut().unwrap().entity.should_be_removed = value;
+            world.cells.master_list[cell_index].0.as_mut().unwrap().entity.should_be_removed = value;
         }
 
         ChangeUpdate::ChangeCellFatExtraEnergy (cell_index, value) => {
-            let cell = program_data.cells.master_list[cell_index].0.as_mut().unwrap();
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
             if let RawCell::Fat (fat_cell_data) = &mut cell.raw_cell {
                 fat_cell_data.extra_energy += value;
             }
         }
 
         ChangeUpdate::ChangeCellFatExtraMaterial (cell_index, value) => {
-            let cell = program_data.cells.master_list[cell_index].0.as_mut().unwrap();
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
             if let RawCell::Fat (fat_cell_data) = &mut cell.raw_cell {
                 fat_cell_data.extra_material += value;
             }
         }
 
+        ChangeUpdate::EatFood (cell_index, food_index, energy, material) => {
+            let food = world.food.master_list[food_index].0.as_mut().unwrap();
+            let (energy, material) = (energy.min(food.energy), material.min(food.material));
+            food.energy -= energy;
+            food.material -= material;
+            food.update_size();
+            if food.energy <= 0. && food.material <= 0. {
+                food.entity.should_be_removed = true;
+            }
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            cell.energy += energy;
+            cell.material += material;
+        }
+
+        ChangeUpdate::ChangeConnectionHealth (connection_id, value) => {
+            let Some(connection) = world.connections.get_mut(connection_id) else {return;};
+            connection.health = (connection.health + value).min(1.);
+            if connection.health <= 0. {
+                world.disconnect_cells(connection_id);
+            }
+        }
+
+        ChangeUpdate::BreakConnection (connection_id) => {
+            world.disconnect_cells(connection_id);
+        }
+
+        ChangeUpdate::ChangeCellFlagellumPhase (cell_index, value) => {
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            if let RawCell::Flagellum (flagellum_cell_data) = &mut cell.raw_cell {
+                flagellum_cell_data.phase = (flagellum_cell_data.phase + value).fract();
+            }
+        }
+
+        ChangeUpdate::ChangeCellContractorPhase (cell_index, value) => {
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            if let RawCell::Contractor (contractor_cell_data) = &mut cell.raw_cell {
+                contractor_cell_data.phase = (contractor_cell_data.phase + value).fract();
+            }
+        }
+
+        ChangeUpdate::SetCellControllerOutputs (cell_index, value) => {
+            world.cells.master_list[cell_index].0.as_mut().unwrap().controller_outputs = value;
+        }
+
+        ChangeUpdate::SetCellSensorValue (cell_index, value) => {
+            world.cells.master_list[cell_index].0.as_mut().unwrap().sensor_value = value;
+        }
+
+        ChangeUpdate::ChangeCellSignal (cell_index, channel, value) => {
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            cell.signals[channel] = (cell.signals[channel] + value).max(0.);
+        }
+
+        ChangeUpdate::SetCellFoodSensorDirection (cell_index, value) => {
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            if let RawCell::FoodSensor (food_sensor_cell_data) = &mut cell.raw_cell {
+                food_sensor_cell_data.direction = value;
+            }
+        }
+
+        ChangeUpdate::ChangeCellEggStoredEnergy (cell_index, value) => {
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            if let RawCell::Egg (egg_cell_data) = &mut cell.raw_cell {
+                egg_cell_data.stored_energy += value;
+            }
+        }
+
+        ChangeUpdate::ChangeCellEggStoredMaterial (cell_index, value) => {
+            let cell = world.cells.master_list[cell_index].0.as_mut().unwrap();
+            if let RawCell::Egg (egg_cell_data) = &mut cell.raw_cell {
+                egg_cell_data.stored_material += value;
+            }
+        }
+
+    }
+}
+
+
+
+// hatching eggs give their offspring everything they have once every change has been applied,
+// so that energy & material transferred into them during the same step isn't lost when they're removed
+pub fn collect_hatched_egg_resources (all_additions: &mut [Vec<AdditionUpdate>], world: &World) {
+    for addition in all_additions.iter_mut().flatten() {
+        let AdditionUpdate::Offspring (offspring_data) = addition else {continue;};
+        let Some(egg_cell_index) = offspring_data.egg_cell_index else {continue;};
+        let egg_cell = world.cells.master_list[egg_cell_index].0.as_ref().unwrap();
+        let cell_count = offspring_data.genome.cells.len() as f64;
+        offspring_data.energy = egg_cell.get_total_energy() / cell_count;
+        offspring_data.material = egg_cell.get_total_material() / cell_count;
     }
 }
 
 
 
-pub fn apply_addition_update (update: AdditionUpdate, program_data: &mut ProgramData) {
+pub fn apply_addition_update (update: AdditionUpdate, world: &mut World) {
     match update {
 
         AdditionUpdate::Food (food) => {
-            program_data.food.add_entity(food);
+            world.food.add_entity(food);
+        }
+
+        AdditionUpdate::Connection (cell_id_1, cell_id_2, energy_cost) => {
+            // several cells can try to make the same connection in one frame, and the cells can be gone by now
+            let (Some(cell_1), Some(cell_2)) = (world.cells.get(cell_id_1), world.cells.get(cell_id_2)) else {return;};
+            if cell_1.is_connected_to(cell_id_2, &world.connections) {return;}
+            let max_connections = world.settings.adhesion_max_connections as usize;
+            if cell_1.connections.len() >= max_connections || cell_2.connections.len() >= max_connections {return;}
+            world.cells.get_mut(cell_id_1).unwrap().energy -= energy_cost;
+            world.connect_cells(Connection::new(cell_id_1, cell_id_2));
+        }
+
+        AdditionUpdate::Offspring (offspring_data) => {
+            let cell_ids = Genome::build_organism(&offspring_data.genome, offspring_data.origin, offspring_data.energy, offspring_data.material, world);
+            // if there's no room, the offspring's resources are left as food so they aren't lost
+            if cell_ids.is_none() {
+                let cell_count = offspring_data.genome.cells.len() as f64;
+                let pos = world.size.constrain_pos(offspring_data.origin);
+                world.food.add_entity(Food::new(pos.0, pos.1, offspring_data.energy * cell_count, offspring_data.material * cell_count));
+            }
         }
 
     }
@@ -211,9 +373,11 @@ pub enum CellUpdateResult {
     Removed,
 }
 
-pub fn update_single_cell (curr_cell_id: EntityID, cells: &EntityContainer<Cell>, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup, dt: f64) -> CellUpdateResult {
+pub fn update_single_cell (curr_cell_id: EntityID, context: &UpdateContext, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup) -> CellUpdateResult {
+    let (cells, settings, dt) = (context.cells, context.settings, context.dt);
 
     let cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
+    let (grid_width, grid_height) = (cells.size.width as f64, cells.size.height as f64);
 
     //-----------------------//
     //        ALWAYS:        //
@@ -225,36 +389,43 @@ pub fn update_single_cell (curr_cell_id: EntityID, cells: &EntityContainer<Cell>
     y += cell.y_vel * dt;
     if cell.entity.x.is_nan() || cell.entity.y.is_nan() {panic!("nan pos")}
     if cell.entity.x.is_infinite() || cell.entity.y.is_infinite() {panic!("infinite pos")}
-    x = x.clamp(0., GRID_WIDTH  as f64 - 0.000001);
-    y = y.clamp(0., GRID_HEIGHT as f64 - 0.000001);
+    (x, y) = cells.size.constrain_pos((x, y));
     world_updates.push_change(ChangeUpdate::SetCellPos (curr_cell_id.0, x, y));
 
     // drag
-    let x_drag = cell.x_vel * cell.x_vel * cell.x_vel.signum() * CELL_DRAG_COEF;
-    let y_drag = cell.y_vel * cell.y_vel * cell.y_vel.signum() * CELL_DRAG_COEF;
+    let drag_coef = if matches!(cell.raw_cell, RawCell::Armor (_)) {settings.cell_drag_coef * settings.armor_drag_mult} else {settings.cell_drag_coef};
+    let x_drag = cell.x_vel * cell.x_vel * cell.x_vel.signum() * drag_coef;
+    let y_drag = cell.y_vel * cell.y_vel * cell.y_vel.signum() * drag_coef;
     cell_changes_group.x_vel_change -= x_drag * dt;
     cell_changes_group.y_vel_change -= y_drag * dt;
 
-    // constrain pos
-    if cell.entity.x < 0.5 {
-        let dist = 0.5 - cell.entity.x;
-        let force = (1. - dist).sqrt() * CELL_INTERSECTION_FORCE;
-        cell_changes_group.x_vel_change += force * dt;
-    }
-    if cell.entity.x > GRID_WIDTH as f64 - 0.5 {
-        let dist = cell.entity.x - GRID_WIDTH as f64 + 0.5;
-        let force = (1. - dist).sqrt() * CELL_INTERSECTION_FORCE;
-        cell_changes_group.x_vel_change += force * dt;
+    // signal decay
+    for (signal_change, signal) in cell_changes_group.signal_changes.iter_mut().zip(cell.signals) {
+        *signal_change -= signal * settings.signal_decay_rate * dt;
     }
-    if cell.entity.y < 0.5 {
-        let dist = 0.5 - cell.entity.y;
-        let force = (1. - dist).sqrt() * CELL_INTERSECTION_FORCE;
-        cell_changes_group.y_vel_change += force * dt;
-    }
-    if cell.entity.y > GRID_HEIGHT as f64 - 0.5 {
-        let dist = cell.entity.y - GRID_HEIGHT as f64 + 0.5;
-        let force = (1. - dist).sqrt() * CELL_INTERSECTION_FORCE;
-        cell_changes_group.y_vel_change += force * dt;
+
+    // constrain pos (walls only, wrapped worlds have no edges)
+    if !cells.size.wraps() {
+        if cell.entity.x < 0.5 {
+            let dist = 0.5 - cell.entity.x;
+            let force = (1. - dist).sqrt() * settings.cell_intersection_force;
+            cell_changes_group.x_vel_change += force * dt;
+        }
+        if cell.entity.x > grid_width - 0.5 {
+            let dist = cell.entity.x - grid_width + 0.5;
+            let force = (1. - dist).sqrt() * settings.cell_intersection_force;
+            cell_changes_group.x_vel_change += force * dt;
+        }
+        if cell.entity.y < 0.5 {
+            let dist = 0.5 - cell.entity.y;
+            let force = (1. - dist).sqrt() * settings.cell_intersection_force;
+            cell_changes_group.y_vel_change += force * dt;
+        }
+        if cell.entity.y > grid_height - 0.5 {
+            let dist = cell.entity.y - grid_height + 0.5;
+            let force = (1. - dist).sqrt() * settings.cell_intersection_force;
+            cell_changes_group.y_vel_change += force * dt;
+        }
     }
 
     // dying
@@ -274,14 +445,21 @@ pub fn update_single_cell (curr_cell_id: EntityID, cells: &EntityContainer<Cell>
     //--------------------------//
 
     // energy drain
-    cell_changes_group.energy_change -= CELL_ENERGY_USE_RATE * dt;
+    cell_changes_group.energy_change -= settings.cell_energy_use_rate * dt;
 
     // healing
     if cell.health < 1. {
-        let heal_amount = (1. - cell.health).min(CELL_HEALING_RATE);
+        let heal_amount = (1. - cell.health).min(settings.cell_healing_rate);
         world_updates.push_change(ChangeUpdate::ChangeCellHealth (curr_cell_id.0, heal_amount * dt));
-        cell_changes_group.energy_change -= heal_amount * CELL_HEALING_ENERGY_COST * dt;
-        cell_changes_group.material_change -= heal_amount * CELL_HEALING_MATERIAL_COST * dt;
+        cell_changes_group.energy_change -= heal_amount * settings.cell_healing_energy_cost * dt;
+        cell_changes_group.material_change -= heal_amount * settings.cell_healing_material_cost * dt;
+    }
+
+    // signal emission
+    let type_emission = cell.raw_cell.get_signal_emission(cell.sensor_value);
+    let controller_emission = &cell.controller_outputs[CONTROLLER_OUTPUT_EMIT_SIGNALS .. CONTROLLER_OUTPUT_EMIT_SIGNALS + SIGNAL_CHANNEL_COUNT];
+    for ((signal_change, type_emission), controller_emission) in cell_changes_group.signal_changes.iter_mut().zip(type_emission).zip(controller_emission) {
+        *signal_change += (type_emission * settings.sensor_signal_emission_rate + controller_emission * settings.signal_emission_rate) * dt;
     }
 
     CellUpdateResult::Alive
@@ -291,9 +469,48 @@ pub fn update_single_cell (curr_cell_id: EntityID, cells: &EntityContainer<Cell>
 
 
 
-pub fn update_cell_by_type (curr_cell_id: EntityID, cells: &EntityContainer<Cell>, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup, dt: f64) {
+// runs the cell's controller (from its genome), the outputs are used by the other update functions next frame
+pub fn update_controller (curr_cell_id: EntityID, context: &UpdateContext, world_updates: &mut WorldUpdates) {
+    let (cells, connections) = (context.cells, context.connections);
     let cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
     if !cell.is_active {return;}
+    let Some(genome) = &cell.genome else {return;};
+
+    let (mut connected_activity, mut connected_sensor_value) = (0., 0.);
+    for &connection_id in &cell.connections {
+        let connected_cell_id = connections.get(connection_id).unwrap().get_other_cell_id(curr_cell_id);
+        let connected_cell = cells.master_list[connected_cell_id.0].0.as_ref().unwrap();
+        connected_activity += connected_cell.get_activity();
+        connected_sensor_value += connected_cell.sensor_value;
+    }
+    if !cell.connections.is_empty() {
+        connected_activity /= cell.connections.len() as f64;
+        connected_sensor_value /= cell.connections.len() as f64;
+    }
+
+    let mut inputs = [0.; CONTROLLER_INPUT_COUNT];
+    inputs[CONTROLLER_INPUT_ENERGY] = cell.energy;
+    inputs[CONTROLLER_INPUT_MATERIAL] = cell.material;
+    inputs[CONTROLLER_INPUT_HEALTH] = cell.health;
+    inputs[CONTROLLER_INPUT_CONNECTED_ACTIVITY] = connected_activity;
+    inputs[CONTROLLER_INPUT_SENSOR] = cell.sensor_value;
+    inputs[CONTROLLER_INPUT_CONNECTED_SENSOR] = connected_sensor_value;
+    inputs[CONTROLLER_INPUT_SIGNALS .. CONTROLLER_INPUT_SIGNALS + SIGNAL_CHANNEL_COUNT].copy_from_slice(&cell.signals);
+    let outputs = genome.cells[cell.genome_index].controller.evaluate(&inputs);
+    if outputs != cell.controller_outputs {
+        world_updates.push_change(ChangeUpdate::SetCellControllerOutputs (curr_cell_id.0, outputs));
+    }
+
+}
+
+
+
+
+
+pub fn update_cell_by_type (curr_cell_id: EntityID, context: &UpdateContext, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup, rng: &mut SimRng) -> CellUpdateResult {
+    let (cells, food, connections, settings, dt) = (context.cells, context.food, context.connections, context.settings, context.dt);
+    let cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
+    if !cell.is_active {return CellUpdateResult::Alive;}
     match &cell.raw_cell {
 
         RawCell::Fat (cell_data) => {
@@ -305,7 +522,7 @@ pub fn update_cell_by_type (curr_cell_id: EntityID, cells: &EntityContainer<Cell
             } else if cell.energy < cell_data.energy_release_threshold {
                 let transfer_amount = cell_data.extra_energy.min(cell_data.energy_release_rate) * dt;
                 cell_changes_group.energy_change += transfer_amount;
-                world_updates.push_change(ChangeUpdate::ChangeCellFatExtraEnergy (curr_cell_id.0, transfer_amount * -1.));
+                world_updates.push_change(ChangeUpdate::ChangeCellFatExtraEnergy (curr_cell_id.0, -transfer_amount));
             }
             if cell.material > cell_data.material_store_threshold {
                 let transfer_amount = (cell.material - cell_data.material_store_threshold).min(cell_data.material_store_rate) * dt;
@@ -314,31 +531,212 @@ pub fn update_cell_by_type (curr_cell_id: EntityID, cells: &EntityContainer<Cell
             } else if cell.material < cell_data.material_release_threshold {
                 let transfer_amount = cell_data.extra_material.min(cell_data.material_release_rate) * dt;
                 cell_changes_group.material_change += transfer_amount;
-                world_updates.push_change(ChangeUpdate::ChangeCellFatExtraMaterial (curr_cell_id.0, transfer_amount * -1.));
+                world_updates.push_change(ChangeUpdate::ChangeCellFatExtraMaterial (curr_cell_id.0, -transfer_amount));
             }
         }
 
         RawCell::Photosynthesiser => {
-            if cell.energy >= 1.0 {return;}
-            let photosynthesis_amount = (1.0 - cell.energy).min(CELL_PHOTOSYNTHESISER_RATE) * dt;
+            if cell.energy >= 1.0 {return CellUpdateResult::Alive;}
+            let light_level = get_light_level(cell.entity.y, &cells.size, settings);
+            let photosynthesis_amount = (1.0 - cell.energy).min(settings.photosynthesiser_rate * light_level) * dt;
             cell_changes_group.energy_change += photosynthesis_amount;
         }
 
+        RawCell::Mouth => {
+            // eat from the closest touching food
+            let grid_pos = (cell.entity.current_grid_x, cell.entity.current_grid_y);
+            let mut closest_food = None;
+            for food_id in fns::get_entity_ids_near_pos(grid_pos, food) {
+                let food_item = food.get(food_id).unwrap();
+                let dist = fns::vec_len(cells.size.get_pos_change((cell.entity.x, cell.entity.y), (food_item.entity.x, food_item.entity.y)));
+                if dist > 0.5 + food_item.entity.width / 2. {continue;}
+                if closest_food.is_none_or(|(_, closest_dist)| dist < closest_dist) {
+                    closest_food = Some((food_id, dist));
+                }
+            }
+            let Some((food_id, _)) = closest_food else {return CellUpdateResult::Alive;};
+            // the amounts are limited to what the food has left when the change is applied, since other mouths can eat from the same food
+            world_updates.push_change(ChangeUpdate::EatFood (curr_cell_id.0, food_id.0, settings.mouth_eat_rate * dt, settings.mouth_eat_rate * dt));
+        }
+
+        RawCell::Flagellum (cell_data) => {
+            world_updates.push_change(ChangeUpdate::ChangeCellFlagellumPhase (curr_cell_id.0, cell_data.frequency * dt));
+
+            // thrust points from the flagellum towards the cells it's connected to
+            let mut direction = (0., 0.);
+            for &connection_id in &cell.connections {
+                let connected_cell_id = connections.get(connection_id).unwrap().get_other_cell_id(curr_cell_id);
+                let connected_cell = cells.master_list[connected_cell_id.0].0.as_ref().unwrap();
+                let dp = cell.pos_change_to(connected_cell, &cells.size);
+                direction.0 += dp.0;
+                direction.1 += dp.1;
+            }
+            let direction_len = fns::vec_len(direction);
+            if direction_len == 0. {return CellUpdateResult::Alive;}
+            let thrust = cell_data.strength * (0.5 + 0.5 * (cell_data.phase * std::f64::consts::TAU).cos()) * cell.get_activity();
+            cell_changes_group.x_vel_change += direction.0 / direction_len * thrust * dt;
+            cell_changes_group.y_vel_change += direction.1 / direction_len * thrust * dt;
+            cell_changes_group.energy_change -= thrust * settings.flagellum_energy_cost * dt;
+        }
+
+        RawCell::Contractor (cell_data) => {
+            // the contraction itself is applied in update_connected_cells(), since both cells of a connection need to see it
+            world_updates.push_change(ChangeUpdate::ChangeCellContractorPhase (curr_cell_id.0, cell_data.frequency * dt));
+            let connection_count = cell.connections.len() as f64;
+            cell_changes_group.energy_change -= cell_data.contraction * cell.get_activity() * cell_data.frequency * connection_count * settings.contractor_energy_cost * dt;
+        }
+
+        RawCell::Adhesive => {
+            // connections are made in update_nearby_cells(), since that's where touching cells are found
+        }
+
+        RawCell::FoodSensor (cell_data) => {
+            // find the closest food in range
+            let grid_pos = (cell.entity.current_grid_x, cell.entity.current_grid_y);
+            let mut closest_food = None;
+            for food_id in fns::get_entity_ids_in_range(grid_pos, cell_data.range.ceil() as usize, food) {
+                let food_item = food.get(food_id).unwrap();
+                let dp = cells.size.get_pos_change((cell.entity.x, cell.entity.y), (food_item.entity.x, food_item.entity.y));
+                let dist = fns::vec_len(dp);
+                if dist > cell_data.range {continue;}
+                if closest_food.is_none_or(|(_, closest_dist)| dist < closest_dist) {
+                    closest_food = Some((dp, dist));
+                }
+            }
+            let (sensor_value, direction) = match closest_food {
+                Some((dp, dist)) if dist > 0. => (1. - dist / cell_data.range, (dp.0 / dist, dp.1 / dist)),
+                Some(_) => (1., (0., 0.)),
+                None => (0., (0., 0.)),
+            };
+            world_updates.push_change(ChangeUpdate::SetCellSensorValue (curr_cell_id.0, sensor_value));
+            world_updates.push_change(ChangeUpdate::SetCellFoodSensorDirection (curr_cell_id.0, direction));
+        }
+
+        RawCell::LightSensor => {
+            let light_level = get_light_level(cell.entity.y, &cells.size, settings);
+            world_updates.push_change(ChangeUpdate::SetCellSensorValue (curr_cell_id.0, light_level));
+        }
+
+        RawCell::ContactSensor => {
+            // the sensor value is set in update_nearby_cells(), since that's where touching cells are found
+        }
+
+        RawCell::Spike (_) => {
+            // damage is dealt in update_nearby_cells(), since that's where touching cells are found
+        }
+
+        RawCell::Armor (cell_data) => {
+            // upkeep (the damage reduction itself is applied with ChangeUpdate::ChangeCellHealth)
+            cell_changes_group.energy_change -= cell_data.damage_reduction * settings.armor_energy_upkeep * dt;
+            cell_changes_group.material_change -= cell_data.damage_reduction * settings.armor_material_upkeep * dt;
+        }
+
+        RawCell::Egg (cell_data) => {
+            // hatch logic
+            if let Some(genome) = &cell.genome {
+                let can_afford = |genome: &Genome| {
+                    cell_data.stored_energy >= genome.get_energy_cost(settings) && cell_data.stored_material >= genome.get_material_cost(settings)
+                };
+                if can_afford(genome) {
+                    let mut offspring_genome = (**genome).clone();
+                    offspring_genome.mutate(settings, rng);
+                    if can_afford(&offspring_genome) {
+                        // the offspring is placed next to the parent organism like with update_reproduction(), the egg's offset in the genome gives the parent's origin
+                        let egg_offset = genome.cells[cell.genome_index].offset;
+                        let parent_origin = (cell.entity.x - egg_offset.0, cell.entity.y - egg_offset.1);
+                        let origin = get_offspring_origin(parent_origin, genome.get_radius(), &offspring_genome, rng);
+                        // everything the egg has goes to the offspring (added up in collect_hatched_egg_resources())
+                        world_updates.push_change(ChangeUpdate::SetCellShouldBeRemoved (curr_cell_id.0, true));
+                        world_updates.push_addition(AdditionUpdate::Offspring (OffspringData {
+                            genome: Arc::new(offspring_genome),
+                            origin,
+                            energy: 0.,
+                            material: 0.,
+                            egg_cell_index: Some(curr_cell_id.0),
+                        }));
+                        return CellUpdateResult::Removed;
+                    }
+                }
+            }
+
+            // store logic (keeping the egg's own energy & material low makes connected cells keep transferring to it)
+            if cell.energy > settings.egg_energy_absorb_threshold {
+                let transfer_amount = (cell.energy - settings.egg_energy_absorb_threshold).min(settings.egg_absorb_rate) * dt;
+                cell_changes_group.energy_change -= transfer_amount;
+                world_updates.push_change(ChangeUpdate::ChangeCellEggStoredEnergy (curr_cell_id.0, transfer_amount));
+            }
+            if cell.material > 0. {
+                let transfer_amount = cell.material.min(settings.egg_absorb_rate) * dt;
+                cell_changes_group.material_change -= transfer_amount;
+                world_updates.push_change(ChangeUpdate::ChangeCellEggStoredMaterial (curr_cell_id.0, transfer_amount));
+            }
+        }
+
     }
+
+    CellUpdateResult::Alive
 }
 
 
 
 
 
-pub fn update_connected_cells (curr_cell_id: EntityID, cells: &EntityContainer<Cell>, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup, dt: f64) {
+// the organism's root cell pays for the whole (mutated) offspring at once, which is then placed next to it in a random direction
+pub fn update_reproduction (curr_cell_id: EntityID, context: &UpdateContext, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup, rng: &mut SimRng) {
+    let (cells, organisms, settings) = (context.cells, context.organisms, context.settings);
     let cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
+    if !cell.is_active || cell.genome_index != 0 {return;}
+    if cell.controller_outputs[CONTROLLER_OUTPUT_REPRODUCE] < 0.5 {return;}
+    let Some(genome) = &cell.genome else {return;};
+
+    let can_afford = |genome: &Genome| {
+        cell.energy >= genome.get_energy_cost(settings) + settings.reproduction_energy_reserve && cell.material >= genome.get_material_cost(settings)
+    };
+    if !can_afford(genome) {return;}
+
+    // the offspring's genome can end up bigger than the parent's, so the cost is checked again after mutating
+    let mut offspring_genome = (**genome).clone();
+    offspring_genome.mutate(settings, rng);
+    if !can_afford(&offspring_genome) {return;}
+    cell_changes_group.energy_change -= offspring_genome.get_energy_cost(settings);
+    cell_changes_group.material_change -= offspring_genome.get_material_cost(settings);
+
+    // the offspring is placed just outside the parent organism's current body, which can differ from its genome after splits & merges
+    let organism = cell.organism_id.and_then(|organism_id| organisms.get(organism_id));
+    let (center, radius) = match organism {
+        Some(organism) => (organism.center_of_mass, organism.radius),
+        None => ((cell.entity.x, cell.entity.y), genome.get_radius()),
+    };
+    let origin = get_offspring_origin(center, radius, &offspring_genome, rng);
+    world_updates.push_addition(AdditionUpdate::Offspring (OffspringData {
+        genome: Arc::new(offspring_genome),
+        origin,
+        energy: settings.reproduction_cell_energy,
+        material: settings.reproduction_cell_material,
+        egg_cell_index: None,
+    }));
+
+}
+
+// a random spot just outside the parent organism, far enough that the two don't overlap
+pub fn get_offspring_origin (parent_origin: (f64, f64), parent_radius: f64, offspring_genome: &Genome, rng: &mut SimRng) -> (f64, f64) {
+    let angle = rng.range_f64(0., std::f64::consts::TAU);
+    let distance = parent_radius + offspring_genome.get_radius() + 0.5;
+    (parent_origin.0 + angle.cos() * distance, parent_origin.1 + angle.sin() * distance)
+}
+
+
 
-    // get connected cells
-    let connected_cell_ids = &cell.connected_cells;
+
+
+pub fn update_connected_cells (curr_cell_id: EntityID, context: &UpdateContext, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup) {
+    let (cells, connections, settings, dt) = (context.cells, context.connections, context.settings, context.dt);
+    let cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
 
     // connected cells
-    for &connected_cell_id in connected_cell_ids {
+    for &connection_id in &cell.connections {
+        let connection = connections.get(connection_id).unwrap();
+        let connected_cell_id = connection.get_other_cell_id(curr_cell_id);
         let connected_cell = cells.master_list[connected_cell_id.0].0.as_ref().unwrap();
 
         //-----------------------//
@@ -346,18 +744,24 @@ pub fn update_connected_cells (curr_cell_id: EntityID, cells: &EntityContainer<C
         //-----------------------//
 
         // spring
-        let dp = cell.pos_change_to(connected_cell);
+        let dp = cell.pos_change_to(connected_cell, &cells.size);
         let dv = fns::move_point_to_line(cell.vel_change_to(connected_cell), dp);
         let dp_len = fns::vec_len(dp);
         let dv_len = fns::vec_len(dv);
-        let force_from_dist = (CELL_CONNECTION_DISTANCE - dp_len) * CELL_CONNECTION_FORCE;
-        let force_from_dist_x = dp.0 * force_from_dist * -1.;
-        let force_from_dist_y = dp.1 * force_from_dist * -1.;
-        let force_from_drag_x = dv.0 * dv_len * CELL_CONNECTION_DRAG;
-        let force_from_drag_y = dv.1 * dv_len * CELL_CONNECTION_DRAG;
+        let rest_length = get_connection_rest_length(cell, connected_cell, connection, settings);
+        let force_from_dist = (rest_length - dp_len) * settings.cell_connection_force * connection.stiffness_scale;
+        let force_from_dist_x = -(dp.0 * force_from_dist);
+        let force_from_dist_y = -(dp.1 * force_from_dist);
+        let force_from_drag_x = dv.0 * dv_len * settings.cell_connection_drag * connection.damping_scale;
+        let force_from_drag_y = dv.1 * dv_len * settings.cell_connection_drag * connection.damping_scale;
         cell_changes_group.x_vel_change += (force_from_dist_x + force_from_drag_x) * dt;
         cell_changes_group.y_vel_change += (force_from_dist_y + force_from_drag_y) * dt;
 
+        // strain (only handled by one of the two cells)
+        if curr_cell_id.0 < connected_cell_id.0 {
+            update_connection_strain(connection_id, connection, dp_len, rest_length, settings, world_updates, dt);
+        }
+
         if !cell.is_active {continue;}
 
         //--------------------------//
@@ -365,26 +769,70 @@ pub fn update_connected_cells (curr_cell_id: EntityID, cells: &EntityContainer<C
         //--------------------------//
 
         // transfers
-        if cell.energy > connected_cell.energy + CELL_ENERGY_TRANSFER_THRESHOLD {
-            let transfer_amount = (cell.energy - connected_cell.energy) * CELL_ENERGY_TRANSFER_RATE * dt;
+        if cell.energy > connected_cell.energy + settings.cell_energy_transfer_threshold {
+            let transfer_amount = (cell.energy - connected_cell.energy) * settings.cell_energy_transfer_rate * connection.permeability * dt;
             cell_changes_group.energy_change -= transfer_amount;
             world_updates.push_change(ChangeUpdate::ChangeCellEnergy (connected_cell_id.0, transfer_amount));
         }
-        if cell.material > connected_cell.material + CELL_MATERIAL_TRANSFER_THRESHOLD {
-            let transfer_amount = (cell.material - connected_cell.material) * CELL_MATERIAL_TRANSFER_RATE * dt;
+        if cell.material > connected_cell.material + settings.cell_material_transfer_threshold {
+            let transfer_amount = (cell.material - connected_cell.material) * settings.cell_material_transfer_rate * connection.permeability * dt;
             cell_changes_group.material_change -= transfer_amount;
             world_updates.push_change(ChangeUpdate::ChangeCellMaterial (connected_cell_id.0, transfer_amount));
         }
+        for channel in 0..SIGNAL_CHANNEL_COUNT {
+            if cell.signals[channel] <= connected_cell.signals[channel] {continue;}
+            let transfer_amount = (cell.signals[channel] - connected_cell.signals[channel]) * settings.signal_diffusion_rate * connection.permeability * dt;
+            cell_changes_group.signal_changes[channel] -= transfer_amount;
+            world_updates.push_change(ChangeUpdate::ChangeCellSignal (connected_cell_id.0, channel, transfer_amount));
+        }
+
+    }
+
+}
+
+
 
+
+
+// connections snap when they're stretched or compressed too far, and take damage when they're strained for too long
+pub fn update_connection_strain (connection_id: ConnectionID, connection: &Connection, length: f64, rest_length: f64, settings: &SimSettings, world_updates: &mut WorldUpdates, dt: f64) {
+    // the rest length can't be 0 (see MAX_CONTRACTION and the connection_rest_length_scale bounds)
+    let strain = (length - rest_length) / rest_length;
+    if strain > settings.connection_max_stretch || -strain > settings.connection_max_compression {
+        world_updates.push_change(ChangeUpdate::BreakConnection (connection_id));
+        return;
+    }
+    let stress = strain.abs() - settings.connection_stress_threshold;
+    if stress > 0. {
+        world_updates.push_change(ChangeUpdate::ChangeConnectionHealth (connection_id, -(stress * settings.connection_stress_damage * dt)));
+    } else if connection.health < 1. {
+        world_updates.push_change(ChangeUpdate::ChangeConnectionHealth (connection_id, settings.connection_healing_rate * dt));
     }
+}
+
+
+
+// contractor cells on either end shorten the connection
+pub fn get_connection_rest_length (cell: &Cell, connected_cell: &Cell, connection: &Connection, settings: &SimSettings) -> f64 {
+    let contraction_mult = (1. - cell.get_contraction()) * (1. - connected_cell.get_contraction());
+    settings.cell_connection_distance * connection.rest_length_scale * contraction_mult
+}
+
+
+
+
 
+// see SimSettings.light_level_top
+pub fn get_light_level (y: f64, world_size: &WorldSize, settings: &SimSettings) -> f64 {
+    settings.light_level_top.lerp(settings.light_level_bottom, y / world_size.height as f64)
 }
 
 
 
 
 
-pub fn update_nearby_cells (curr_cell_id: EntityID, cells: &EntityContainer<Cell>, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup, dt: f64) {
+pub fn update_nearby_cells (curr_cell_id: EntityID, context: &UpdateContext, world_updates: &mut WorldUpdates, cell_changes_group: &mut CellChangesGroup) {
+    let (cells, connections, settings, dt) = (context.cells, context.connections, context.settings, context.dt);
     let cell = cells.master_list[curr_cell_id.0].0.as_ref().unwrap();
     let grid_pos = (cell.entity.current_grid_x, cell.entity.current_grid_y);
 
@@ -394,15 +842,52 @@ pub fn update_nearby_cells (curr_cell_id: EntityID, cells: &EntityContainer<Cell
     nearby_cell_ids.swap_remove(curr_cell_id_index);
 
     // intersection force
+    let mut foreign_contact_count = 0;
     for nearby_cell_id in nearby_cell_ids {
         let other_cell = cells.master_list[nearby_cell_id.0].0.as_ref().unwrap();
-        let dist_vec = cell.pos_change_to(other_cell);
+        let dist_vec = cell.pos_change_to(other_cell, &cells.size);
         let dist = fns::vec_len(dist_vec);
         if dist > 1. {continue;}
-        let force = (1. - dist).sqrt() * CELL_INTERSECTION_FORCE;
+        let force = (1. - dist).sqrt() * settings.cell_intersection_force;
         let force_vec = (dist_vec.0 * force, dist_vec.1 * force);
         cell_changes_group.x_vel_change -= force_vec.0 * dt;
         cell_changes_group.y_vel_change -= force_vec.1 * dt;
+        if cell.organism_id != other_cell.organism_id {
+            foreign_contact_count += 1;
+
+            // spike damage
+            if let RawCell::Spike (spike_cell_data) = &cell.raw_cell {
+                if cell.is_active && cell.energy > 0. {
+                    let damage = spike_cell_data.damage * cell.get_activity() * dt;
+                    world_updates.push_change(ChangeUpdate::ChangeCellHealth (nearby_cell_id.0, -damage));
+                    cell_changes_group.energy_change -= damage * settings.spike_energy_cost;
+                }
+            }
+
+        }
+
+        // adhesion
+        if should_adhere(cell, other_cell, nearby_cell_id, connections, settings) {
+            world_updates.push_addition(AdditionUpdate::Connection (curr_cell_id, nearby_cell_id, settings.adhesion_energy_cost));
+        }
+
     }
 
+    // contact sensor (the number of touching cells from other organisms)
+    if cell.is_active && matches!(cell.raw_cell, RawCell::ContactSensor) {
+        world_updates.push_change(ChangeUpdate::SetCellSensorValue (curr_cell_id.0, foreign_contact_count as f64));
+    }
+
+}
+
+
+
+// adhesive cells connect to the cells they touch
+pub fn should_adhere (cell: &Cell, other_cell: &Cell, other_cell_id: EntityID, connections: &ConnectionContainer, settings: &SimSettings) -> bool {
+    if !cell.is_active || !matches!(cell.raw_cell, RawCell::Adhesive) {return false;}
+    if cell.energy < settings.adhesion_energy_cost {return false;}
+    let max_connections = settings.adhesion_max_connections as usize;
+    if cell.connections.len() >= max_connections || other_cell.connections.len() >= max_connections {return false;}
+    if settings.adhesion_same_organism_only && (cell.organism_id.is_none() || cell.organism_id != other_cell.organism_id) {return false;}
+    !cell.is_connected_to(other_cell_id, connections)
 }
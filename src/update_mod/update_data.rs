@@ -4,6 +4,18 @@ use crate::prelude::*;
 
 
 
+// everything a cell's update can read from, shared by every cell in the main pass
+pub struct UpdateContext<'a> {
+    pub cells: &'a EntityContainer<Cell>,
+    pub food: &'a EntityContainer<Food>,
+    pub connections: &'a ConnectionContainer,
+    pub organisms: &'a OrganismContainer,
+    pub settings: &'a SimSettings,
+    pub dt: f64,
+}
+
+
+
 pub struct WorldUpdates {
     pub changes: Vec<ChangeUpdate>,
     pub additions: Vec<AdditionUpdate>,
@@ -20,11 +32,24 @@ pub enum ChangeUpdate {
     SetCellShouldBeRemoved (usize, bool),
     ChangeCellFatExtraEnergy (usize, f64),
     ChangeCellFatExtraMaterial (usize, f64),
+    EatFood (usize, usize, f64, f64), // cell index, food index, energy, material
+    ChangeCellEggStoredEnergy (usize, f64),
+    ChangeCellEggStoredMaterial (usize, f64),
+    ChangeConnectionHealth (ConnectionID, f64),
+    BreakConnection (ConnectionID),
+    ChangeCellFlagellumPhase (usize, f64),
+    ChangeCellContractorPhase (usize, f64),
+    SetCellControllerOutputs (usize, ControllerOutputs),
+    SetCellSensorValue (usize, f64),
+    SetCellFoodSensorDirection (usize, (f64, f64)),
+    ChangeCellSignal (usize, usize, f64), // cell index, channel, value
 
 }
 
 pub enum AdditionUpdate {
     Food (Food),
+    Connection (EntityID, EntityID, f64), // the cell that pays, the other cell, energy cost
+    Offspring (OffspringData),
 }
 
 
@@ -44,6 +69,12 @@ impl WorldUpdates {
     }
 }
 
+impl Default for WorldUpdates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 
 
 
@@ -53,6 +84,7 @@ pub struct CellChangesGroup {
     pub y_vel_change: f64,
     pub energy_change: f64,
     pub material_change: f64,
+    pub signal_changes: SignalValues,
 }
 
 impl CellChangesGroup {
@@ -61,7 +93,8 @@ impl CellChangesGroup {
             x_vel_change: 0.,
             y_vel_change: 0.,
             energy_change: 0.,
-            material_change: 0.
+            material_change: 0.,
+            signal_changes: [0.; SIGNAL_CHANNEL_COUNT],
         }
     }
     pub fn add_self_to_world_updates (self, all_updates: &mut WorldUpdates, cell_id: EntityID) {
@@ -70,5 +103,86 @@ impl CellChangesGroup {
         if self.material_change != 0. {
             all_updates.changes.push(ChangeUpdate::ChangeCellMaterial (cell_id.0, self.material_change));
         }
+        for (channel, &signal_change) in self.signal_changes.iter().enumerate() {
+            if signal_change == 0. {continue;}
+            all_updates.changes.push(ChangeUpdate::ChangeCellSignal (cell_id.0, channel, signal_change));
+        }
+    }
+}
+
+impl Default for CellChangesGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Copy)]
+pub enum ProfilePhase {
+    MainPass,
+    ApplyChanges,
+    SyncFeilds,
+    ApplyAdditions,
+    Organisms,
+}
+
+// total time spent in each phase of update_cells (in seconds)
+#[derive(Debug, Clone)]
+pub struct ProfileData {
+    pub step_count: u64,
+    pub main_pass: f64,
+    pub apply_changes: f64,
+    pub sync_feilds: f64,
+    pub apply_additions: f64,
+    pub organisms: f64,
+}
+
+impl ProfileData {
+    pub fn new() -> Self {
+        Self {
+            step_count: 0,
+            main_pass: 0.,
+            apply_changes: 0.,
+            sync_feilds: 0.,
+            apply_additions: 0.,
+            organisms: 0.,
+        }
+    }
+    pub fn add_phase_time (&mut self, phase: ProfilePhase, time: f64) {
+        match phase {
+            ProfilePhase::MainPass => self.main_pass += time,
+            ProfilePhase::ApplyChanges => self.apply_changes += time,
+            ProfilePhase::SyncFeilds => self.sync_feilds += time,
+            ProfilePhase::ApplyAdditions => self.apply_additions += time,
+            ProfilePhase::Organisms => self.organisms += time,
+        }
+    }
+}
+
+impl Default for ProfileData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ProfileData {
+    fn fmt (&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let step_count = self.step_count.max(1) as f64;
+        let total = self.main_pass + self.apply_changes + self.sync_feilds + self.apply_additions + self.organisms;
+        writeln!(f, "Profiled steps: {}", self.step_count)?;
+        writeln!(f, "Phase            total (s)   avg per step (ms)")?;
+        for (name, time) in [
+            ("main pass", self.main_pass),
+            ("apply changes", self.apply_changes),
+            ("sync feilds", self.sync_feilds),
+            ("apply additions", self.apply_additions),
+            ("organisms", self.organisms),
+            ("total", total),
+        ] {
+            writeln!(f, "{name:<16} {time:<11.4} {:.4}", time / step_count * 1000.)?;
+        }
+        Ok(())
     }
 }
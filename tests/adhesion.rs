@@ -0,0 +1,91 @@
+// Adhesive cells connect to the cells they touch, paying a bit of energy for each connection
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// an adhesive cell in the middle of the world, with mouth cells (which do nothing without food) at `other_positions`
+fn create_sticky_world (settings: SimSettings, adhesive_energy: f64, other_positions: &[(f64, f64)]) -> (World, EntityID) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..settings};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let adhesive_id = world.cells.add_entity(Cell::new(RawCell::new_adhesive_cell(), 10., 10., 1., adhesive_energy, 0.)).unwrap();
+    for &(x, y) in other_positions {
+        world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), x, y, 1., 0., 0.)).unwrap();
+    }
+    (world, adhesive_id)
+}
+
+fn get_connection_count (world: &World) -> usize {
+    world.connections.list.iter().filter(|connection_data| connection_data.0.is_some()).count()
+}
+
+
+
+
+
+#[test]
+fn adhesive_cell_connects_to_touching_cells() {
+    let (mut world, adhesive_id) = create_sticky_world(SimSettings::default(), 1., &[(10.8, 10.), (15., 10.)]);
+
+    world.step(DT);
+
+    assert_eq!(get_connection_count(&world), 1, "only the touching cell should be connected");
+    let adhesive = world.cells.get(adhesive_id).unwrap();
+    assert_eq!(adhesive.connections.len(), 1);
+    assert!((adhesive.energy - (1. - world.settings.adhesion_energy_cost)).abs() < 1e-9);
+
+    // it doesn't connect to the same cell again
+    world.step(DT);
+    assert_eq!(get_connection_count(&world), 1);
+}
+
+#[test]
+fn adhesion_needs_energy() {
+    let settings = SimSettings::default();
+    let energy = settings.adhesion_energy_cost / 2.;
+    let (mut world, _) = create_sticky_world(settings, energy, &[(10.8, 10.)]);
+
+    world.step(DT);
+
+    assert_eq!(get_connection_count(&world), 0);
+}
+
+#[test]
+fn adhesion_stops_at_max_connections() {
+    let settings = SimSettings {adhesion_max_connections: 2, ..SimSettings::default()};
+    let (mut world, adhesive_id) = create_sticky_world(settings, 1., &[(10.8, 10.), (9.2, 10.), (10., 10.8), (10., 9.2)]);
+
+    for _ in 0..5 {
+        world.step(DT);
+    }
+
+    assert_eq!(world.cells.get(adhesive_id).unwrap().connections.len(), 2);
+    assert_eq!(get_connection_count(&world), 2);
+}
+
+#[test]
+fn same_organism_only_ignores_other_organisms() {
+    let settings = SimSettings {adhesion_same_organism_only: true, ..SimSettings::default()};
+    let (mut world, adhesive_id) = create_sticky_world(settings, 1., &[(10.8, 10.), (9.2, 10.), (10.4, 10.9)]);
+    // the cell on the right is part of the adhesive cell's organism (through the cell above them), the cell on the left isn't
+    let cell_ids = world.cells.master_list.iter().enumerate().map(|(i, cell_data)| (i, cell_data.1)).collect::<Vec<EntityID>>();
+    world.connect_cells(Connection::new(adhesive_id, cell_ids[3]));
+    world.connect_cells(Connection::new(cell_ids[3], cell_ids[1]));
+
+    // organisms are found at the end of the first step
+    world.step(DT);
+    world.step(DT);
+
+    let adhesive = world.cells.get(adhesive_id).unwrap();
+    assert_eq!(adhesive.connections.len(), 2);
+    assert!(adhesive.is_connected_to(cell_ids[1], &world.connections));
+    assert!(!adhesive.is_connected_to(cell_ids[2], &world.connections));
+}
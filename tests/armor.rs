@@ -0,0 +1,66 @@
+// Armor cells take less damage from spikes, but have more drag and cost energy & material to keep up
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// one cell of each type in `raw_cells`, placed left to right 0.8 apart (so neighbours touch) with full energy & material
+fn create_armor_world (raw_cells: impl IntoIterator<Item = RawCell>) -> (World, Vec<EntityID>) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let cell_ids = raw_cells.into_iter().enumerate()
+        .map(|(i, raw_cell)| world.cells.add_entity(Cell::new(raw_cell, 10. + i as f64 * 0.8, 10., 1., 1., 1.)).unwrap())
+        .collect();
+    (world, cell_ids)
+}
+
+
+
+
+
+#[test]
+fn armor_reduces_spike_damage() {
+    let settings = SimSettings::default();
+    let (mut world, cell_ids) = create_armor_world([RawCell::new_spike_cell(&settings), RawCell::new_armor_cell(&settings)]);
+
+    // organisms are found at the end of the first step, so the spike only attacks on the second one
+    world.step(DT);
+    world.step(DT);
+
+    let damage = settings.spike_damage.default * DT * (1. - settings.armor_damage_reduction.default);
+    assert!((world.cells.get(cell_ids[1]).unwrap().health - (1. - damage)).abs() < 1e-9);
+}
+
+#[test]
+fn armor_pays_upkeep() {
+    let settings = SimSettings::default();
+    let (mut world, cell_ids) = create_armor_world([RawCell::new_armor_cell(&settings)]);
+
+    world.step(DT);
+
+    let armor = world.cells.get(cell_ids[0]).unwrap();
+    let damage_reduction = settings.armor_damage_reduction.default;
+    assert!((armor.energy - (1. - damage_reduction * settings.armor_energy_upkeep * DT)).abs() < 1e-9);
+    assert!((armor.material - (1. - damage_reduction * settings.armor_material_upkeep * DT)).abs() < 1e-9);
+}
+
+#[test]
+fn armor_slows_down_faster() {
+    let get_speed_after_step = |raw_cell: RawCell| {
+        let (mut world, cell_ids) = create_armor_world([raw_cell]);
+        world.cells.get_mut(cell_ids[0]).unwrap().x_vel = 2.;
+        world.step(DT);
+        world.cells.get(cell_ids[0]).unwrap().x_vel
+    };
+    let armor_speed = get_speed_after_step(RawCell::new_armor_cell(&SimSettings::default()));
+    let mouth_speed = get_speed_after_step(RawCell::new_mouth_cell());
+    assert!(armor_speed < mouth_speed);
+}
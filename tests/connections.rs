@@ -0,0 +1,102 @@
+// Connections are stored once in the world's connection list, and both of their cells list them
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// two connected cells with no energy use (and no food), so the only changes come from the connection
+fn create_pair_world (connection_fn: impl FnOnce(Connection) -> Connection) -> (World, [EntityID; 2], ConnectionID) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let distance = settings.cell_connection_distance;
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let cell_id_1 = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 10., 10., 1., 1., 0.)).unwrap();
+    let cell_id_2 = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 10. + distance, 10., 1., 0., 0.)).unwrap();
+    let connection_id = world.connect_cells(connection_fn(Connection::new(cell_id_1, cell_id_2)));
+    (world, [cell_id_1, cell_id_2], connection_id)
+}
+
+fn get_live_connection_count (world: &World) -> usize {
+    world.connections.list.iter().filter(|connection_data| connection_data.0.is_some()).count()
+}
+
+
+
+
+
+#[test]
+fn both_cells_list_the_same_connection() {
+    let (mut world, [cell_id_1, cell_id_2], connection_id) = create_pair_world(|connection| connection);
+    assert_eq!(world.cells.get(cell_id_1).unwrap().connections, vec!(connection_id));
+    assert_eq!(world.cells.get(cell_id_2).unwrap().connections, vec!(connection_id));
+    assert_eq!(get_live_connection_count(&world), 1);
+
+    world.disconnect_cells(connection_id);
+
+    assert!(world.cells.get(cell_id_1).unwrap().connections.is_empty());
+    assert!(world.cells.get(cell_id_2).unwrap().connections.is_empty());
+    assert!(world.connections.get(connection_id).is_none());
+    // disconnecting twice does nothing
+    world.disconnect_cells(connection_id);
+}
+
+#[test]
+fn dead_cells_lose_their_connections() {
+    let (mut world, [cell_id_1, cell_id_2], connection_id) = create_pair_world(|connection| connection);
+    world.cells.get_mut(cell_id_2).unwrap().health = 0.;
+
+    // the cell is removed during the first step, and its connection at the start of the next one
+    world.step(DT);
+    world.step(DT);
+
+    assert!(world.cells.get(cell_id_2).is_none());
+    assert!(world.connections.get(connection_id).is_none());
+    assert!(world.cells.get(cell_id_1).unwrap().connections.is_empty());
+    assert_eq!(get_live_connection_count(&world), 0);
+}
+
+#[test]
+fn permeability_scales_transfers() {
+    let get_transferred_energy = |permeability: f64| {
+        let (mut world, [_, cell_id_2], _) = create_pair_world(|connection| Connection {permeability, ..connection});
+        world.step(DT);
+        world.cells.get(cell_id_2).unwrap().energy
+    };
+    assert_eq!(get_transferred_energy(0.), 0.);
+    let transferred_energy = get_transferred_energy(1.);
+    assert!(transferred_energy > 0.);
+    assert!((get_transferred_energy(2.) - transferred_energy * 2.).abs() < 1e-12);
+}
+
+#[test]
+fn damping_scale_slows_cells_moving_apart() {
+    let get_relative_speed = |damping_scale: f64| {
+        let (mut world, [cell_id_1, cell_id_2], _) = create_pair_world(|connection| Connection {damping_scale, ..connection});
+        world.cells.get_mut(cell_id_2).unwrap().x_vel = 1.;
+        world.step(DT);
+        world.cells.get(cell_id_2).unwrap().x_vel - world.cells.get(cell_id_1).unwrap().x_vel
+    };
+    assert!(get_relative_speed(4.) < get_relative_speed(1.));
+    assert!(get_relative_speed(1.) < get_relative_speed(0.));
+}
+
+#[test]
+fn connections_survive_a_round_trip() {
+    let (world, _, connection_id) = create_pair_world(|connection| Connection {rest_length_scale: 1.5, permeability: 0.5, ..connection});
+    let mut writer = SnapshotWriter::new();
+    world.write_snapshot(&mut writer);
+    let mut reader = SnapshotReader::new(&writer.output);
+
+    let loaded_world = World::read_snapshot(&mut reader).unwrap();
+
+    let (connection, loaded_connection) = (world.connections.get(connection_id).unwrap(), loaded_world.connections.get(connection_id).unwrap());
+    assert_eq!(loaded_connection.cell_ids, connection.cell_ids);
+    assert_eq!((loaded_connection.rest_length_scale, loaded_connection.permeability), (1.5, 0.5));
+}
@@ -0,0 +1,100 @@
+// Contractor cells shorten their connections, and each connection has its own rest length and stiffness
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+const STEPS: usize = 200;
+
+
+
+
+
+// two cells at the default connection distance, with no energy use
+fn create_pair_world (raw_cell: RawCell, rest_length_scale: f64, stiffness_scale: f64) -> World {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let distance = settings.cell_connection_distance;
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let cell_id = world.cells.add_entity(Cell::new(raw_cell, 10., 10., 1., 0.5, 0.)).unwrap();
+    let other_id = world.cells.add_entity(Cell::new(RawCell::new_photosynthesiser_cell(), 10. + distance, 10., 1., 0.5, 0.)).unwrap();
+    world.connect_cells(Connection {rest_length_scale, stiffness_scale, ..Connection::new(cell_id, other_id)});
+    world
+}
+
+fn get_pair_distance (world: &World) -> f64 {
+    let cells = world.cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()).collect::<Vec<&Cell>>();
+    cells[0].distance_to(cells[1], &world.size)
+}
+
+fn create_contractor_cell (settings: &SimSettings, contraction: f64, frequency: f64) -> RawCell {
+    let mut raw_cell = RawCell::new_contractor_cell(settings);
+    let RawCell::Contractor (contractor_cell_data) = &mut raw_cell else {unreachable!()};
+    (contractor_cell_data.contraction, contractor_cell_data.frequency) = (contraction, frequency);
+    raw_cell
+}
+
+
+
+
+
+#[test]
+fn contractor_shortens_its_connection() {
+    let settings = SimSettings::default();
+    // a longer connection, so the contracted one is still long enough that the cells don't push each other apart
+    let mut world = create_pair_world(create_contractor_cell(&settings, 0.25, 0.), 1.5, 1.);
+    // phase 0.5 is full contraction, and with a frequency of 0 it stays there
+    let RawCell::Contractor (contractor_cell_data) = &mut world.cells.master_list[0].0.as_mut().unwrap().raw_cell else {unreachable!()};
+    contractor_cell_data.phase = 0.5;
+    let mut relaxed_world = create_pair_world(create_contractor_cell(&settings, 0.25, 0.), 1.5, 1.);
+
+    for _ in 0..STEPS * 5 {
+        world.step(DT);
+        relaxed_world.step(DT);
+    }
+
+    let rest_length = settings.cell_connection_distance * 1.5;
+    assert!((get_pair_distance(&world) - rest_length * 0.75).abs() < 0.05, "expected the contracted length, got {}", get_pair_distance(&world));
+    assert!((get_pair_distance(&relaxed_world) - rest_length).abs() < 0.05, "expected the relaxed length, got {}", get_pair_distance(&relaxed_world));
+}
+
+#[test]
+fn contracting_costs_energy() {
+    let settings = SimSettings::default();
+    let mut world = create_pair_world(create_contractor_cell(&settings, 0.5, 1.), 1., 1.);
+    let mut still_world = create_pair_world(create_contractor_cell(&settings, 0.5, 0.), 1., 1.);
+
+    world.step(DT);
+    still_world.step(DT);
+
+    let get_energy = |world: &World| world.cells.master_list[0].0.as_ref().unwrap().energy;
+    let energy_cost = get_energy(&still_world) - get_energy(&world);
+    // contraction * frequency * connection count * cost
+    assert!((energy_cost - 0.5 * 1. * 1. * settings.contractor_energy_cost * DT).abs() < 1e-9);
+}
+
+#[test]
+fn connections_use_their_own_rest_length() {
+    let mut world = create_pair_world(RawCell::new_photosynthesiser_cell(), 1.5, 1.);
+
+    for _ in 0..STEPS * 5 {
+        world.step(DT);
+    }
+
+    let distance = world.settings.cell_connection_distance;
+    assert!((get_pair_distance(&world) - distance * 1.5).abs() < 0.05, "expected the cells to settle at the longer rest length, got {}", get_pair_distance(&world));
+}
+
+#[test]
+fn stiffer_connections_pull_harder() {
+    let get_pull_speed = |stiffness_scale: f64| {
+        let mut world = create_pair_world(RawCell::new_photosynthesiser_cell(), 1.5, stiffness_scale);
+        world.step(DT);
+        world.cells.master_list[1].0.as_ref().unwrap().x_vel
+    };
+    let (soft_speed, stiff_speed) = (get_pull_speed(1.), get_pull_speed(2.));
+    assert!(soft_speed > 0.);
+    assert!((stiff_speed - soft_speed * 2.).abs() < 1e-9);
+}
@@ -0,0 +1,86 @@
+// Genome controllers turn a cell's state into its activity (which drives flagella & contractors) and whether it reproduces
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// a cell with a one-cell genome using `controller`, connected to a mouth cell (which does nothing without food) on its right
+// the cell starts with every output at 0, so it only acts on its controller's outputs from the second step on
+fn create_controlled_world (raw_cell: RawCell, controller: Controller, energy: f64, material: f64) -> (World, EntityID) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let distance = settings.cell_connection_distance;
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let genome = Genome::new(vec!(GenomeCell {controller, ..GenomeCell::new(raw_cell.clone(), (0., 0.))}), vec!());
+    let mut cell = Cell::new(raw_cell, 10., 10., 1., energy, material);
+    cell.genome = Some(Arc::new(genome));
+    cell.controller_outputs = [0.; CONTROLLER_OUTPUT_COUNT];
+    let cell_id = world.cells.add_entity(cell).unwrap();
+    let other_id = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 10. + distance, 10., 1., 0., 0.)).unwrap();
+    world.connect_cells(Connection::new(cell_id, other_id));
+    (world, cell_id)
+}
+
+fn with_bias (output_index: usize, bias: f64) -> Controller {
+    let mut controller = Controller::new();
+    controller.biases[output_index] = bias;
+    controller
+}
+
+
+
+
+
+#[test]
+fn outputs_follow_the_weighted_inputs() {
+    let mut controller = with_bias(CONTROLLER_OUTPUT_ACTIVITY, 0.);
+    controller.weights[CONTROLLER_OUTPUT_ACTIVITY][CONTROLLER_INPUT_ENERGY] = 0.5;
+    let (mut world, cell_id) = create_controlled_world(RawCell::new_mouth_cell(), controller, 0.8, 0.);
+
+    world.step(DT);
+
+    let cell = world.cells.get(cell_id).unwrap();
+    assert!((cell.get_activity() - 0.4).abs() < 1e-9);
+    // the reproduce output still has the default bias of 1
+    assert_eq!(cell.controller_outputs[CONTROLLER_OUTPUT_REPRODUCE], 1.);
+}
+
+#[test]
+fn inactive_flagella_dont_push() {
+    let get_speed_and_energy = |controller: Controller| {
+        let settings = SimSettings::default();
+        let (mut world, cell_id) = create_controlled_world(RawCell::new_flagellum_cell(&settings), controller, 1., 0.);
+        world.step(DT);
+        world.step(DT);
+        let cell = world.cells.get(cell_id).unwrap();
+        (cell.x_vel.abs(), cell.energy)
+    };
+
+    // some energy is shared with the connected cell either way, only thrust costs more
+    let (inactive_speed, inactive_energy) = get_speed_and_energy(with_bias(CONTROLLER_OUTPUT_ACTIVITY, 0.));
+    let (active_speed, active_energy) = get_speed_and_energy(Controller::new());
+    assert!(inactive_speed < 1e-12);
+    assert!(active_speed > 0.01);
+    assert!(active_energy < inactive_energy);
+}
+
+#[test]
+fn reproduction_waits_for_the_reproduce_output() {
+    let count_cells_after_steps = |controller: Controller| {
+        let (mut world, _) = create_controlled_world(RawCell::new_mouth_cell(), controller, 100., 100.);
+        for _ in 0..3 {
+            world.step(DT);
+        }
+        world.cells.master_list.iter().filter(|cell_data| cell_data.0.is_some()).count()
+    };
+
+    assert_eq!(count_cells_after_steps(with_bias(CONTROLLER_OUTPUT_REPRODUCE, 0.4)), 2);
+    assert!(count_cells_after_steps(Controller::new()) > 2);
+}
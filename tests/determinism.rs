@@ -0,0 +1,83 @@
+// The simulation should give the same result no matter how many threads rayon uses
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const TICK_COUNT: u64 = 150;
+const DT: f64 = 0.016;
+
+
+
+
+
+// every value that can change while stepping, as raw bits so that even the smallest floating point difference is caught
+fn get_world_state (world: &World) -> Vec<u64> {
+    let mut output = vec!(world.frame_count);
+    for (cell, generation) in &world.cells.master_list {
+        output.push(*generation as u64);
+        let Some(cell) = cell else {continue;};
+        for value in [cell.entity.x, cell.entity.y, cell.x_vel, cell.y_vel, cell.energy, cell.material, cell.health] {
+            output.push(value.to_bits());
+        }
+    }
+    for (food, generation) in &world.food.master_list {
+        output.push(*generation as u64);
+        let Some(food) = food else {continue;};
+        for value in [food.entity.x, food.entity.y, food.energy, food.material] {
+            output.push(value.to_bits());
+        }
+    }
+    output
+}
+
+fn run_with_thread_count (thread_count: usize) -> Vec<u64> {
+    let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(thread_count).build().unwrap();
+    thread_pool.install(|| {
+        let mut world = World::new(WorldSize::default(), SimSettings::default(), 0);
+        world.add_test_data();
+        for _ in 0..TICK_COUNT {
+            world.step(DT);
+        }
+        get_world_state(&world)
+    })
+}
+
+
+
+
+
+#[test]
+fn same_result_with_any_thread_count() {
+    let single_thread_state = run_with_thread_count(1);
+    for thread_count in [2, 4, 8] {
+        assert!(run_with_thread_count(thread_count) == single_thread_state, "{thread_count} threads gave a different result than 1 thread");
+    }
+}
+
+#[test]
+fn fixed_timestep_step_counts() {
+    // these values are exact in binary, so there's no rounding to worry about
+    let mut fixed_timestep = FixedTimestep::new(0.0625);
+    assert_eq!(fixed_timestep.get_step_count(0.21875, 1.), 3);
+    // the leftover half step carries over to the next frame
+    assert_eq!(fixed_timestep.get_step_count(0.03125, 1.), 1);
+    assert_eq!(fixed_timestep.get_step_count(0., 1.), 0);
+    // long frames only catch up by FIXED_TIMESTEP_MAX_CATCHUP
+    assert_eq!(fixed_timestep.get_step_count(10., 1.), (FIXED_TIMESTEP_MAX_CATCHUP / 0.0625) as u32);
+    // the time scale multiplies the elapsed time, so a quarter speed frame only adds a quarter step
+    let mut fixed_timestep = FixedTimestep::new(0.0625);
+    assert_eq!(fixed_timestep.get_step_count(0.125, 4.), 8);
+    assert_eq!(fixed_timestep.get_step_count(0.125, 0.25), 0);
+    assert_eq!(fixed_timestep.get_step_count(0.125, 0.25), 1);
+}
+
+#[test]
+fn invalid_dt_is_rejected() {
+    for value in ["0", "-0.016", "NaN", "inf", "fast"] {
+        assert!(fns::parse_dt_arg_value("--dt", Some(String::from(value))).is_err(), "\"{value}\" was accepted");
+    }
+    assert_eq!(fns::parse_dt_arg_value("--dt", Some(String::from("0.016"))).unwrap(), 0.016);
+}
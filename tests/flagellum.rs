@@ -0,0 +1,74 @@
+// Flagellum cells push towards the cells they're connected to, and pay energy for it
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// a flagellum connected to a photosynthesiser on its right, with nothing else that uses energy
+fn create_swimmer_world (flagellum_strength: f64) -> World {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let mut flagellum_cell = RawCell::new_flagellum_cell(&world.settings);
+    let RawCell::Flagellum (flagellum_cell_data) = &mut flagellum_cell else {unreachable!()};
+    flagellum_cell_data.strength = flagellum_strength;
+    let flagellum_id = world.cells.add_entity(Cell::new(flagellum_cell, 10., 10., 1., 0.5, 0.)).unwrap();
+    let other_id = world.cells.add_entity(Cell::new(RawCell::new_photosynthesiser_cell(), 11., 10., 1., 0.5, 0.)).unwrap();
+    world.connect_cells(Connection::new(flagellum_id, other_id));
+    world
+}
+
+fn get_flagellum (world: &World) -> &Cell {
+    world.cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()).find(|cell| matches!(cell.raw_cell, RawCell::Flagellum (_))).unwrap()
+}
+
+
+
+
+
+#[test]
+fn flagellum_pushes_towards_its_connected_cell() {
+    let mut world = create_swimmer_world(2.);
+    let mut still_world = create_swimmer_world(0.);
+
+    world.step(DT);
+    still_world.step(DT);
+
+    let (flagellum, still_flagellum) = (get_flagellum(&world), get_flagellum(&still_world));
+    // the phase starts at 0, so the first step gets the full strength
+    assert!((flagellum.x_vel - still_flagellum.x_vel - 2. * DT).abs() < 1e-9);
+    assert!((flagellum.y_vel - still_flagellum.y_vel).abs() < 1e-9);
+}
+
+#[test]
+fn thrust_costs_energy() {
+    let mut world = create_swimmer_world(2.);
+    let mut still_world = create_swimmer_world(0.);
+
+    world.step(DT);
+    still_world.step(DT);
+
+    let energy_cost = get_flagellum(&still_world).energy - get_flagellum(&world).energy;
+    assert!((energy_cost - 2. * world.settings.flagellum_energy_cost * DT).abs() < 1e-9);
+}
+
+#[test]
+fn unconnected_flagellum_does_nothing() {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    world.cells.add_entity(Cell::new(RawCell::new_flagellum_cell(&world.settings), 10., 10., 1., 0.5, 0.)).unwrap();
+
+    for _ in 0..10 {
+        world.step(DT);
+    }
+
+    let flagellum = get_flagellum(&world);
+    assert_eq!((flagellum.x_vel, flagellum.y_vel, flagellum.energy), (0., 0., 0.5));
+}
@@ -0,0 +1,189 @@
+// Genome mutations have to keep the genome valid: connections point at existing cells, the root cell stays, and the history stays bounded
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const MUTATION_ROUNDS: u64 = 200;
+
+
+
+
+
+// settings where nothing mutates, so each test can turn on just the mutation it's checking
+fn get_settings_without_mutations() -> SimSettings {
+    SimSettings {
+        mutation_parameter_chance: 0.,
+        mutation_cell_type_chance: 0.,
+        mutation_add_cell_chance: 0.,
+        mutation_remove_cell_chance: 0.,
+        mutation_add_connection_chance: 0.,
+        mutation_remove_connection_chance: 0.,
+        mutation_controller_chance: 0.,
+        ..SimSettings::default()
+    }
+}
+
+// a chain of cells along the x axis, so each cell can be recognised by its offset
+fn create_line_genome (cell_count: usize, settings: &SimSettings) -> Genome {
+    let cells = (0..cell_count).map(|i| GenomeCell::new(RawCell::new_fat_cell(settings), (i as f64, 0.))).collect();
+    let connections = (1..cell_count).map(|i| GenomeConnection::new(i - 1, i)).collect();
+    Genome::new(cells, connections)
+}
+
+fn check_connections (genome: &Genome) {
+    for (i, connection) in genome.connections.iter().enumerate() {
+        let (cell_index_1, cell_index_2) = connection.cell_indices;
+        assert!(cell_index_1 < genome.cells.len() && cell_index_2 < genome.cells.len(), "connection {i} points past the end of the cell list");
+        assert!(cell_index_1 != cell_index_2, "connection {i} connects cell {cell_index_1} to itself");
+        let is_duplicate = genome.connections[..i].iter().any(|connection| connection.cell_indices == (cell_index_1, cell_index_2) || connection.cell_indices == (cell_index_2, cell_index_1));
+        assert!(!is_duplicate, "connection {i} is a duplicate");
+    }
+}
+
+
+
+
+
+#[test]
+fn remove_cell_reindexes_connections() {
+    let settings = SimSettings::default();
+    let mut genome = create_line_genome(4, &settings);
+    genome.connections.extend([GenomeConnection::new(0, 3), GenomeConnection::new(1, 3)]);
+
+    genome.remove_cell(2);
+
+    let offsets = genome.cells.iter().map(|genome_cell| genome_cell.offset.0).collect::<Vec<f64>>();
+    assert_eq!(offsets, vec!(0., 1., 3.));
+    // (1, 2) and (2, 3) are removed with the cell, and the old cell 3 is now cell 2
+    let cell_indices = genome.connections.iter().map(|connection| connection.cell_indices).collect::<Vec<(usize, usize)>>();
+    assert_eq!(cell_indices, vec!((0, 1), (0, 2), (1, 2)));
+}
+
+#[test]
+fn remove_cell_mutations_keep_connections_valid() {
+    let settings = SimSettings {mutation_remove_cell_chance: 1., ..get_settings_without_mutations()};
+    for seed in 0..MUTATION_ROUNDS {
+        let mut genome = create_line_genome(6, &settings);
+        genome.connections.extend([GenomeConnection::new(0, 5), GenomeConnection::new(2, 4), GenomeConnection::new(1, 3)]);
+        let mut rng = SimRng::new(seed);
+        genome.mutate(&settings, &mut rng);
+
+        let Some(MutationRecord {mutation: Mutation::RemoveCell {cell_index}, ..}) = genome.mutation_history.last() else {
+            panic!("seed {seed}: no cell was removed");
+        };
+        assert_eq!(genome.cells.len(), 5);
+        // every cell after the removed one moves down by one, and keeps its connections to the cells that are left
+        let removed_offset = *cell_index as f64;
+        for connection in &genome.connections {
+            let (cell_index_1, cell_index_2) = connection.cell_indices;
+            let (offset_1, offset_2) = (genome.cells[cell_index_1].offset.0, genome.cells[cell_index_2].offset.0);
+            assert!(offset_1 != removed_offset && offset_2 != removed_offset);
+        }
+        check_connections(&genome);
+    }
+}
+
+#[test]
+fn root_cell_is_never_removed() {
+    let settings = SimSettings {mutation_remove_cell_chance: 1., ..get_settings_without_mutations()};
+    let mut genome = create_line_genome(4, &settings);
+    let mut rng = SimRng::new(1);
+    for _ in 0..MUTATION_ROUNDS {
+        genome.mutate(&settings, &mut rng);
+        assert_eq!(genome.cells[0].offset, (0., 0.), "the root cell was removed");
+    }
+    assert_eq!(genome.cells.len(), 1);
+    let removed_count = genome.mutation_history.iter().filter(|record| matches!(record.mutation, Mutation::RemoveCell {..})).count();
+    assert_eq!(removed_count, 3);
+}
+
+#[test]
+fn added_connections_are_never_self_loops_or_duplicates() {
+    let settings = SimSettings {mutation_add_connection_chance: 1., ..get_settings_without_mutations()};
+    for seed in 0..20 {
+        let mut genome = create_line_genome(5, &settings);
+        let mut rng = SimRng::new(seed);
+        for _ in 0..MUTATION_ROUNDS {
+            genome.mutate(&settings, &mut rng);
+            check_connections(&genome);
+        }
+        // 5 cells can have at most 10 connections, and with this many rounds every one of them should have been added
+        assert_eq!(genome.connections.len(), 10);
+    }
+}
+
+#[test]
+fn all_mutations_keep_the_genome_valid() {
+    let settings = SimSettings {
+        mutation_parameter_chance: 0.2,
+        mutation_cell_type_chance: 0.2,
+        mutation_add_cell_chance: 0.5,
+        mutation_remove_cell_chance: 0.5,
+        mutation_add_connection_chance: 0.5,
+        mutation_remove_connection_chance: 0.5,
+        ..SimSettings::default()
+    };
+    let mut genome = Genome::new_test_triad(&settings);
+    let mut rng = SimRng::new(7);
+    for _ in 0..MUTATION_ROUNDS {
+        genome.mutate(&settings, &mut rng);
+        assert!(!genome.cells.is_empty());
+        check_connections(&genome);
+    }
+}
+
+#[test]
+fn connection_parameters_mutate_within_their_ranges() {
+    let settings = SimSettings {mutation_parameter_chance: 1., mutation_parameter_amount: 0.5, ..get_settings_without_mutations()};
+    let mut genome = create_line_genome(3, &settings);
+    let mut rng = SimRng::new(5);
+    for _ in 0..MUTATION_ROUNDS {
+        genome.mutate(&settings, &mut rng);
+        for connection in &genome.connections {
+            let (rest_length_range, stiffness_range) = (&settings.connection_rest_length_scale, &settings.connection_stiffness_scale);
+            assert!((rest_length_range.min..=rest_length_range.max).contains(&connection.rest_length_scale));
+            assert!((stiffness_range.min..=stiffness_range.max).contains(&connection.stiffness_scale));
+            assert!((settings.connection_permeability.min..=settings.connection_permeability.max).contains(&connection.permeability));
+        }
+    }
+    assert!(genome.connections.iter().all(|connection| connection.rest_length_scale != 1. && connection.damping_scale != 1.));
+    // 2 connections with 4 parameters each, all of them mutated in the last round (along with the cells' parameters)
+    let last_round_records = genome.mutation_history.iter().filter(|record| record.generation == genome.generation);
+    assert_eq!(last_round_records.filter(|record| matches!(record.mutation, Mutation::ChangeConnectionParameter {..})).count(), 8);
+}
+
+#[test]
+fn built_organisms_use_the_genome_connection_parameters() {
+    let settings = SimSettings::default();
+    let mut genome = create_line_genome(2, &settings);
+    let genome_connection = GenomeConnection {rest_length_scale: 1.5, stiffness_scale: 0.5, damping_scale: 2., permeability: 0.25, ..genome.connections[0].clone()};
+    genome.connections[0] = genome_connection.clone();
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+
+    let cell_ids = Genome::build_organism(&Arc::new(genome), (10., 10.), 1., 0., &mut world).unwrap();
+
+    let connection_id = world.cells.get(cell_ids[0]).unwrap().connections[0];
+    assert_eq!(world.cells.get(cell_ids[1]).unwrap().connections, vec!(connection_id));
+    let connection = world.connections.get(connection_id).unwrap();
+    assert_eq!(connection.cell_ids, (cell_ids[0], cell_ids[1]));
+    let scales = (connection.rest_length_scale, connection.stiffness_scale, connection.damping_scale, connection.permeability);
+    assert_eq!(scales, (1.5, 0.5, 2., 0.25));
+}
+
+#[test]
+fn mutation_history_is_trimmed() {
+    let settings = SimSettings {mutation_parameter_chance: 1., mutation_history_max_len: 5, ..get_settings_without_mutations()};
+    let mut genome = Genome::new_test_triad(&settings);
+    let mut rng = SimRng::new(3);
+    for _ in 0..10 {
+        genome.mutate(&settings, &mut rng);
+        assert!(genome.mutation_history.len() <= 5);
+    }
+    assert_eq!(genome.generation, 10);
+    assert_eq!(genome.mutation_history.len(), 5);
+    // the oldest records are the ones removed
+    assert!(genome.mutation_history.iter().all(|record| record.generation == 10));
+}
@@ -0,0 +1,80 @@
+// Mouth cells eat from the closest food they're touching
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.1;
+
+
+
+
+
+// a still world with no energy use, so the only changes come from eating
+fn create_feeding_world (mouth_positions: &[(f64, f64)], food: Food) -> World {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(10, 10), settings, 0);
+    for &(x, y) in mouth_positions {
+        world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), x, y, 1., 0.5, 0.)).unwrap();
+    }
+    world.food.add_entity(food).unwrap();
+    world
+}
+
+fn get_mouths (world: &World) -> Vec<&Cell> {
+    world.cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()).collect()
+}
+
+fn get_food (world: &World) -> Option<&Food> {
+    world.food.master_list.iter().find_map(|entity_data| entity_data.0.as_ref())
+}
+
+
+
+
+
+#[test]
+fn mouth_eats_touching_food() {
+    let mut world = create_feeding_world(&[(5., 5.)], Food::new(5.3, 5., 1., 1.));
+    let eat_amount = world.settings.mouth_eat_rate * DT;
+
+    for _ in 0..5 {
+        world.step(DT);
+    }
+
+    let mouth = get_mouths(&world)[0];
+    let food = get_food(&world).unwrap();
+    assert!((mouth.energy - (0.5 + eat_amount * 5.)).abs() < 1e-9);
+    assert!((mouth.material - eat_amount * 5.).abs() < 1e-9);
+    assert!((food.energy - (1. - eat_amount * 5.)).abs() < 1e-9);
+    assert!((food.material - (1. - eat_amount * 5.)).abs() < 1e-9);
+}
+
+#[test]
+fn mouth_ignores_food_out_of_reach() {
+    let mut world = create_feeding_world(&[(5., 5.)], Food::new(7., 5., 1., 1.));
+
+    for _ in 0..5 {
+        world.step(DT);
+    }
+
+    assert_eq!(get_mouths(&world)[0].energy, 0.5);
+    assert_eq!(get_food(&world).unwrap().energy, 1.);
+}
+
+#[test]
+fn mouths_sharing_food_never_eat_more_than_it_has() {
+    // two mouths on either side of a nearly empty piece of food
+    let mut world = create_feeding_world(&[(4.8, 5.), (5.8, 5.)], Food::new(5.3, 5., 0.005, 0.005));
+
+    world.step(DT);
+
+    assert!(get_food(&world).is_none(), "the empty food should have been removed");
+    let mouths = get_mouths(&world);
+    let energy_eaten = mouths.iter().map(|mouth| mouth.energy - 0.5).sum::<f64>();
+    let material_eaten = mouths.iter().map(|mouth| mouth.material).sum::<f64>();
+    assert!((energy_eaten - 0.005).abs() < 1e-12);
+    assert!((material_eaten - 0.005).abs() < 1e-12);
+}
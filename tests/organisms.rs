@@ -0,0 +1,100 @@
+// Every group of connected cells is an organism, which keeps its id through splits & merges
+// (the biggest piece keeps it) and counts them
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// a line of mouth cells (which do nothing without food), each one connected to the next
+fn create_chain_world (cell_count: usize) -> (World, Vec<EntityID>, Vec<ConnectionID>) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let distance = settings.cell_connection_distance;
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let cell_ids = (0..cell_count)
+        .map(|i| world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 5. + i as f64 * distance, 10., 1., 0., 0.)).unwrap())
+        .collect::<Vec<EntityID>>();
+    let connection_ids = cell_ids.windows(2)
+        .map(|pair| world.connect_cells(Connection::new(pair[0], pair[1])))
+        .collect();
+    (world, cell_ids, connection_ids)
+}
+
+fn get_organism_id (world: &World, cell_id: EntityID) -> OrganismID {
+    world.cells.get(cell_id).unwrap().organism_id.unwrap()
+}
+
+
+
+
+
+#[test]
+fn connected_cells_share_an_organism() {
+    let (mut world, cell_ids, _) = create_chain_world(3);
+
+    world.step(DT);
+
+    assert_eq!(world.organisms.iter().count(), 1);
+    let organism_id = get_organism_id(&world, cell_ids[0]);
+    assert!(cell_ids.iter().all(|&cell_id| get_organism_id(&world, cell_id) == organism_id));
+    assert_eq!(world.organisms.get(organism_id).unwrap().cell_ids.len(), 3);
+}
+
+#[test]
+fn splitting_keeps_the_id_on_the_biggest_piece() {
+    let (mut world, cell_ids, connection_ids) = create_chain_world(3);
+    world.step(DT);
+    let organism_id = get_organism_id(&world, cell_ids[0]);
+
+    world.disconnect_cells(connection_ids[1]);
+    world.step(DT);
+
+    assert_eq!((world.organisms.split_count, world.organisms.merge_count), (1, 0));
+    assert_eq!(world.organisms.iter().count(), 2);
+    assert_eq!(get_organism_id(&world, cell_ids[1]), organism_id);
+    let piece = world.organisms.get(get_organism_id(&world, cell_ids[2])).unwrap();
+    assert_eq!(piece.parent_id, Some(organism_id));
+    assert_eq!(piece.cell_ids, vec!(cell_ids[2]));
+}
+
+#[test]
+fn merging_removes_the_smaller_organism() {
+    let (mut world, cell_ids, connection_ids) = create_chain_world(3);
+    world.disconnect_cells(connection_ids[1]);
+    world.step(DT);
+    let (organism_id, other_organism_id) = (get_organism_id(&world, cell_ids[0]), get_organism_id(&world, cell_ids[2]));
+    assert_ne!(organism_id, other_organism_id);
+
+    world.connect_cells(Connection::new(cell_ids[1], cell_ids[2]));
+    world.step(DT);
+
+    assert_eq!((world.organisms.split_count, world.organisms.merge_count), (0, 1));
+    assert!(world.organisms.get(other_organism_id).is_none());
+    assert_eq!(get_organism_id(&world, cell_ids[2]), organism_id);
+    assert_eq!(world.get_stats().organism_count, 1);
+}
+
+#[test]
+fn organisms_survive_a_round_trip() {
+    let (mut world, cell_ids, connection_ids) = create_chain_world(3);
+    world.step(DT);
+    world.disconnect_cells(connection_ids[1]);
+    world.step(DT);
+    let mut writer = SnapshotWriter::new();
+    world.write_snapshot(&mut writer);
+    let mut reader = SnapshotReader::new(&writer.output);
+
+    let loaded_world = World::read_snapshot(&mut reader).unwrap();
+
+    assert_eq!(loaded_world.organisms.split_count, 1);
+    assert_eq!(get_organism_id(&loaded_world, cell_ids[2]), get_organism_id(&world, cell_ids[2]));
+    let organism_id = get_organism_id(&world, cell_ids[0]);
+    assert_eq!(loaded_world.organisms.get(organism_id).unwrap().cell_ids.len(), 2);
+}
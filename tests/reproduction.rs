@@ -0,0 +1,120 @@
+// Organisms reproduce from their root cell, building offspring from the same genome
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+fn get_cells (world: &World) -> Vec<&Cell> {
+    world.cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()).collect()
+}
+
+// each organism has one root cell
+fn get_root_cells (world: &World) -> Vec<&Cell> {
+    get_cells(world).into_iter().filter(|cell| cell.genome_index == 0).collect()
+}
+
+
+
+
+
+#[test]
+fn test_data_reproduces_with_default_settings() {
+    let size = WorldSize {max_entities_count: 10_000, ..WorldSize::new(30, 30)};
+    let mut world = World::new(size, SimSettings::default(), 0);
+    world.add_test_data();
+    let start_organism_count = get_root_cells(&world).len();
+    let genome = get_cells(&world)[0].genome.clone().unwrap();
+
+    // nothing is updated during the warmup frames
+    for _ in 0..=world.settings.warmup_frames {
+        world.step(DT);
+    }
+
+    let root_cells = get_root_cells(&world);
+    // a few offspring can get an added cell and cost more material than their parent was seeded with, but most fit
+    assert!(root_cells.len() > start_organism_count * 3 / 2, "the seeded triads should have reproduced");
+    assert!(root_cells.len() <= start_organism_count * 2);
+    for cell in root_cells {
+        let cell_genome = cell.genome.as_ref().unwrap();
+        // offspring get a mutated copy of the genome (which can be an identical copy), the parents keep theirs
+        match cell_genome.generation {
+            0 => assert!(Arc::ptr_eq(cell_genome, &genome)),
+            1 => assert!(!Arc::ptr_eq(cell_genome, &genome)),
+            generation => panic!("unexpected genome generation {generation}"),
+        }
+    }
+}
+
+#[test]
+fn reproduction_needs_material() {
+    let size = WorldSize {max_entities_count: 10_000, ..WorldSize::new(30, 30)};
+    let settings = SimSettings {reproduction_cell_material: 10., ..SimSettings::default()};
+    let mut world = World::new(size, settings, 0);
+    world.add_test_data();
+    for cell_data in &mut world.cells.master_list {
+        cell_data.0.as_mut().unwrap().material = 1.;
+    }
+    let start_cell_count = world.get_stats().cell_count;
+
+    for _ in 0..world.settings.warmup_frames + 10 {
+        world.step(DT);
+    }
+
+    assert_eq!(world.get_stats().cell_count, start_cell_count);
+}
+
+#[test]
+fn egg_hatches_next_to_its_parent_with_everything_it_was_given() {
+    // no energy use, mutations or root cell reproduction, so the only thing that happens is the egg hatching
+    let settings = SimSettings {
+        warmup_frames: 0,
+        cell_energy_use_rate: 0.,
+        reproduction_energy_reserve: 10.,
+        mutation_parameter_chance: 0.,
+        mutation_cell_type_chance: 0.,
+        mutation_add_cell_chance: 0.,
+        mutation_remove_cell_chance: 0.,
+        mutation_add_connection_chance: 0.,
+        mutation_remove_connection_chance: 0.,
+        ..SimSettings::default()
+    };
+    let genome = Arc::new(Genome::new(
+        vec!(
+            GenomeCell::new(RawCell::new_fat_cell(&settings), (0., 0.)),
+            GenomeCell::new(RawCell::new_egg_cell(), (1., 0.)),
+        ),
+        vec!(GenomeConnection::new(0, 1)),
+    ));
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let [root_id, egg_id] = Genome::build_organism(&genome, (10., 10.), 0., 0., &mut world).unwrap()[..] else {unreachable!()};
+    // the root cell has more than the egg, so it transfers some to the egg during the step that the egg hatches in
+    let root = world.cells.get_mut(root_id).unwrap();
+    (root.energy, root.material) = (1., 1.);
+    let egg = world.cells.get_mut(egg_id).unwrap();
+    let RawCell::Egg (egg_cell_data) = &mut egg.raw_cell else {unreachable!()};
+    (egg_cell_data.stored_energy, egg_cell_data.stored_material) = (1., 1.);
+    let start_stats = world.get_stats();
+
+    world.step(0.016);
+
+    let stats = world.get_stats();
+    assert!(world.cells.get(egg_id).is_none(), "the egg should have hatched");
+    assert_eq!(stats.cell_count, 3);
+    assert_eq!(stats.food_count, 0);
+    assert!((stats.cells_energy - start_stats.cells_energy).abs() < 1e-9, "energy was lost when the egg hatched");
+    assert!((stats.cells_material - start_stats.cells_material).abs() < 1e-9, "material was lost when the egg hatched");
+    let root = world.cells.get(root_id).unwrap();
+    assert!(root.energy < 1. && root.material < 1., "the root cell should have transferred some energy and material to the egg");
+    for cell in get_cells(&world) {
+        if std::ptr::eq(cell, root) {continue;}
+        assert!(cell.distance_to(root, &world.size) > 1.5, "the offspring overlaps its parent");
+    }
+}
@@ -0,0 +1,80 @@
+// Sensor cells set their sensor value from the food, light or foreign cells around them, which controllers can read
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// a single sensor cell at `pos`, in a world where cells don't use energy
+fn create_sensor_world (settings: SimSettings, raw_cell: RawCell, pos: (f64, f64)) -> (World, EntityID) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..settings};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let sensor_id = world.cells.add_entity(Cell::new(raw_cell, pos.0, pos.1, 1., 0.5, 0.)).unwrap();
+    (world, sensor_id)
+}
+
+
+
+
+
+#[test]
+fn food_sensor_points_at_the_closest_food() {
+    let settings = SimSettings::default();
+    let range = settings.food_sensor_range.default;
+    let (mut world, sensor_id) = create_sensor_world(settings.clone(), RawCell::new_food_sensor_cell(&settings), (10., 10.));
+    world.food.add_entity(Food::new(10., 13., 1., 1.));
+    world.food.add_entity(Food::new(12., 10., 1., 1.));
+
+    world.step(DT);
+
+    let sensor = world.cells.get(sensor_id).unwrap();
+    assert!((sensor.sensor_value - (1. - 2. / range)).abs() < 1e-9);
+    let RawCell::FoodSensor (sensor_data) = &sensor.raw_cell else {panic!("expected a food sensor")};
+    assert!((sensor_data.direction.0 - 1.).abs() < 1e-9 && sensor_data.direction.1.abs() < 1e-9);
+}
+
+#[test]
+fn food_sensor_ignores_food_out_of_range() {
+    let settings = SimSettings::default();
+    let range = settings.food_sensor_range.default;
+    let (mut world, sensor_id) = create_sensor_world(settings.clone(), RawCell::new_food_sensor_cell(&settings), (5., 10.));
+    world.food.add_entity(Food::new(5. + range + 1., 10., 1., 1.));
+
+    world.step(DT);
+
+    assert_eq!(world.cells.get(sensor_id).unwrap().sensor_value, 0.);
+}
+
+#[test]
+fn light_sensor_follows_the_light_gradient() {
+    let settings = SimSettings {light_level_top: 1., light_level_bottom: 0., ..SimSettings::default()};
+    let get_light = |y: f64| {
+        let (mut world, sensor_id) = create_sensor_world(settings.clone(), RawCell::LightSensor, (10., y));
+        world.step(DT);
+        world.cells.get(sensor_id).unwrap().sensor_value
+    };
+    assert!((get_light(5.) - 0.75).abs() < 1e-9);
+    assert!((get_light(15.) - 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn contact_sensor_only_counts_other_organisms() {
+    let (mut world, sensor_id) = create_sensor_world(SimSettings::default(), RawCell::ContactSensor, (10., 10.));
+    let own_cell_id = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 10.8, 10., 1., 0.5, 0.)).unwrap();
+    world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 9.2, 10., 1., 0.5, 0.)).unwrap();
+    world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 10., 10.8, 1., 0.5, 0.)).unwrap();
+    world.connect_cells(Connection::new(sensor_id, own_cell_id));
+
+    // organisms are found at the end of the first step
+    world.step(DT);
+    world.step(DT);
+
+    assert_eq!(world.cells.get(sensor_id).unwrap().sensor_value, 2.);
+}
@@ -0,0 +1,114 @@
+// Parsing and validation of settings files
+
+
+
+use cell_engine::prelude::*;
+
+
+
+
+
+fn get_errors (input: &str) -> String {
+    match SimSettings::from_settings_string(input) {
+        Ok(_) => panic!("expected the settings to be rejected:\n{input}"),
+        Err(ProgramError::InvalidSettings (errors)) => errors,
+        Err(error) => panic!("expected InvalidSettings, got {error:?}"),
+    }
+}
+
+fn get_temp_path (name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cell_engine_test_{}_{name}.txt", std::process::id()))
+}
+
+
+
+
+
+#[test]
+fn default_settings_round_trip() {
+    let settings_string = SimSettings::default().to_settings_string();
+    assert_eq!(SimSettings::from_settings_string(&settings_string).unwrap(), SimSettings::default());
+    assert!(SimSettings::default().get_validation_errors().is_empty());
+}
+
+#[test]
+fn parses_values_and_ranges() {
+    let settings = SimSettings::from_settings_string("
+        # comments and blank lines are skipped
+
+        cell_drag_coef = 0.25
+        fat_energy_store_threshold = 0.1, 0.9, 0.5
+    ").unwrap();
+    assert_eq!(settings.cell_drag_coef, 0.25);
+    let range = settings.fat_energy_store_threshold;
+    assert_eq!((range.min, range.max, range.default), (0.1, 0.9, 0.5));
+}
+
+#[test]
+fn missing_settings_use_the_defaults() {
+    let settings = SimSettings::from_settings_string("cell_drag_coef = 0.25\n").unwrap();
+    assert_eq!(settings, SimSettings {cell_drag_coef: 0.25, ..SimSettings::default()});
+}
+
+#[test]
+fn parse_errors() {
+    let errors = get_errors("cell_drag_coef = fast\nfat_energy_store_threshold = 0, 1\nthis line has no equals sign\n");
+    // parse errors are reported in the order the settings are visited, so sort them to make the test independent of the field order
+    let mut errors = errors.lines().collect::<Vec<_>>();
+    errors.sort();
+    assert_eq!(errors, vec!(
+        "line 1: cell_drag_coef: \"fast\" is not a number",
+        "line 2: fat_energy_store_threshold: expected \"min, max, default\", got \"0, 1\"",
+        "line 3: expected \"name = value\"",
+    ));
+}
+
+#[test]
+fn unknown_settings_are_errors_in_line_order() {
+    let errors = get_errors("zzz_unknown = 1\ncell_drag_coef = 0.1\naaa_unknown = 2\nmmm_unknown = 3\n");
+    assert_eq!(errors, "line 1: unknown setting \"zzz_unknown\"\nline 3: unknown setting \"aaa_unknown\"\nline 4: unknown setting \"mmm_unknown\"");
+}
+
+#[test]
+fn duplicate_settings_are_errors() {
+    let errors = get_errors("cell_drag_coef = 0.1\n\ncell_drag_coef = 0.2\n");
+    assert_eq!(errors, "line 3: \"cell_drag_coef\" is already set on line 1");
+}
+
+#[test]
+fn validation_errors() {
+    let cases = [
+        ("cell_drag_coef = -0.1", "cell_drag_coef: value must not be negative, got -0.1"),
+        ("cell_drag_coef = inf", "cell_drag_coef: value must be finite, got inf"),
+        ("cell_connection_distance = 0", "cell_connection_distance: value must be greater than 0, got 0"),
+        ("fat_energy_store_threshold = 0.8, 0.2, 0.5", "fat_energy_store_threshold: min (0.8) is greater than max (0.2)"),
+        ("fat_energy_store_threshold = 0.2, 0.8, 0.9", "fat_energy_store_threshold: default (0.9) is not between min (0.2) and max (0.8)"),
+        ("fat_energy_store_threshold = 0.5, 1.5, 1", "fat_energy_store_threshold: value must be between 0 and 1, got 1.5"),
+        ("fat_energy_store_rate = -0.5, 0.2, 0.1", "fat_energy_store_rate: value must not be negative, got -0.5"),
+        ("contractor_contraction = 0, 1, 0.5", "contractor_contraction: value must be between 0 and 0.9, got 1"),
+    ];
+    for (input, expected_error) in cases {
+        assert_eq!(get_errors(input), expected_error, "wrong error for \"{input}\"");
+    }
+}
+
+#[test]
+fn all_errors_are_reported_together() {
+    let errors = get_errors("cell_drag_coef = -1\nunknown_setting = 1\nfat_energy_store_threshold = 0, 2, 1\n");
+    assert_eq!(errors.lines().count(), 3, "{errors}");
+}
+
+#[test]
+fn load_or_default_doesnt_create_files() {
+    let path = get_temp_path("missing_settings");
+    assert_eq!(SimSettings::load_or_default(&path).unwrap(), SimSettings::default());
+    assert!(!path.exists());
+}
+
+#[test]
+fn load_or_create_falls_back_to_the_defaults() {
+    // the parent directory doesn't exist, so the file can't be created
+    let path = get_temp_path("missing_dir").join(SETTINGS_FILE_NAME);
+    assert_eq!(SimSettings::load_or_create(&path).unwrap(), SimSettings::default());
+    assert!(!path.exists());
+}
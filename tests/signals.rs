@@ -0,0 +1,86 @@
+// Chemical signals spread along connections, decay over time, and are emitted by sensors and controllers
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// mouth cells (which do nothing without food) at `positions`, with the first one holding a signal of 1 on channel 0
+fn create_signal_world (settings: SimSettings, positions: &[(f64, f64)]) -> (World, Vec<EntityID>) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..settings};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let cell_ids = positions.iter()
+        .map(|&(x, y)| world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), x, y, 1., 0.5, 0.)).unwrap())
+        .collect::<Vec<EntityID>>();
+    world.cells.get_mut(cell_ids[0]).unwrap().signals[0] = 1.;
+    (world, cell_ids)
+}
+
+fn get_signals (world: &World, cell_id: EntityID) -> SignalValues {
+    world.cells.get(cell_id).unwrap().signals
+}
+
+
+
+
+
+#[test]
+fn signals_diffuse_along_connections() {
+    let settings = SimSettings {signal_decay_rate: 0., ..SimSettings::default()};
+    let distance = settings.cell_connection_distance;
+    let (mut world, cell_ids) = create_signal_world(settings, &[(5., 10.), (5. + distance, 10.), (15., 10.)]);
+    world.connect_cells(Connection::new(cell_ids[0], cell_ids[1]));
+
+    world.step(DT);
+
+    let transfer_amount = world.settings.signal_diffusion_rate * DT;
+    assert!((get_signals(&world, cell_ids[0])[0] - (1. - transfer_amount)).abs() < 1e-9);
+    assert!((get_signals(&world, cell_ids[1])[0] - transfer_amount).abs() < 1e-9);
+    // the other channel and the unconnected cell are untouched
+    assert_eq!(get_signals(&world, cell_ids[1])[1], 0.);
+    assert_eq!(get_signals(&world, cell_ids[2]), [0.; SIGNAL_CHANNEL_COUNT]);
+}
+
+#[test]
+fn impermeable_connections_block_signals() {
+    let settings = SimSettings::default();
+    let distance = settings.cell_connection_distance;
+    let (mut world, cell_ids) = create_signal_world(settings, &[(5., 10.), (5. + distance, 10.)]);
+    world.connect_cells(Connection {permeability: 0., ..Connection::new(cell_ids[0], cell_ids[1])});
+
+    world.step(DT);
+
+    assert_eq!(get_signals(&world, cell_ids[1])[0], 0.);
+}
+
+#[test]
+fn signals_decay() {
+    let settings = SimSettings {signal_decay_rate: 2., ..SimSettings::default()};
+    let (mut world, cell_ids) = create_signal_world(settings, &[(10., 10.)]);
+
+    world.step(DT);
+
+    assert!((get_signals(&world, cell_ids[0])[0] - (1. - 2. * DT)).abs() < 1e-9);
+}
+
+#[test]
+fn light_sensors_emit_on_channel_0() {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., signal_decay_rate: 0., light_level_top: 0.5, light_level_bottom: 0.5, ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let sensor_id = world.cells.add_entity(Cell::new(RawCell::LightSensor, 10., 10., 1., 0.5, 0.)).unwrap();
+
+    // the sensor value is set during the first step and emitted from the second one on
+    world.step(DT);
+    world.step(DT);
+
+    let expected_signal = 0.5 * world.settings.sensor_signal_emission_rate * DT;
+    assert!((get_signals(&world, sensor_id)[0] - expected_signal).abs() < 1e-9);
+    assert_eq!(get_signals(&world, sensor_id)[1], 0.);
+}
@@ -0,0 +1,85 @@
+// Connections snap when they're stretched or compressed too far, wear down under strain and heal when relaxed
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// two mouth cells `distance` apart (no food, so nothing else happens to them), connected by a connection with `health`
+fn create_strained_world (distance: f64, health: f64) -> (World, ConnectionID) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let cell_id_1 = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 5., 10., 1., 0.5, 0.)).unwrap();
+    let cell_id_2 = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 5. + distance, 10., 1., 0.5, 0.)).unwrap();
+    let connection_id = world.connect_cells(Connection {health, ..Connection::new(cell_id_1, cell_id_2)});
+    (world, connection_id)
+}
+
+
+
+
+
+#[test]
+fn overstretched_connections_snap() {
+    let rest_length = SimSettings::default().cell_connection_distance;
+    let max_stretch = SimSettings::default().connection_max_stretch;
+    let (mut world, connection_id) = create_strained_world(rest_length * (1. + max_stretch) + 0.1, 1.);
+
+    world.step(DT);
+
+    assert!(world.connections.get(connection_id).is_none());
+    assert!(world.cells.master_list.iter().all(|cell_data| cell_data.0.as_ref().unwrap().connections.is_empty()));
+}
+
+#[test]
+fn overcompressed_connections_snap() {
+    let rest_length = SimSettings::default().cell_connection_distance;
+    let max_compression = SimSettings::default().connection_max_compression;
+    let (mut world, connection_id) = create_strained_world(rest_length * (1. - max_compression) - 0.1, 1.);
+
+    world.step(DT);
+
+    assert!(world.connections.get(connection_id).is_none());
+}
+
+#[test]
+fn strained_connections_wear_down_and_break() {
+    let settings = SimSettings::default();
+    // a strain of 1 is under connection_max_stretch, but over connection_stress_threshold
+    let strain = 1.;
+    let (mut world, connection_id) = create_strained_world(settings.cell_connection_distance * (1. + strain), 1.);
+
+    world.step(DT);
+
+    let health = world.connections.get(connection_id).unwrap().health;
+    let expected_damage = (strain - settings.connection_stress_threshold) * settings.connection_stress_damage * DT;
+    assert!((health - (1. - expected_damage)).abs() < 1e-9);
+
+    // a worn down connection breaks when its health runs out
+    let (mut world, connection_id) = create_strained_world(settings.cell_connection_distance * (1. + strain), expected_damage / 2.);
+    world.step(DT);
+    assert!(world.connections.get(connection_id).is_none());
+}
+
+#[test]
+fn relaxed_connections_heal() {
+    let settings = SimSettings::default();
+    let (mut world, connection_id) = create_strained_world(settings.cell_connection_distance, 0.5);
+
+    world.step(DT);
+
+    let health = world.connections.get(connection_id).unwrap().health;
+    assert!((health - (0.5 + settings.connection_healing_rate * DT)).abs() < 1e-9);
+
+    // healing stops at full health
+    world.connections.get_mut(connection_id).unwrap().health = 1. - 1e-6;
+    world.step(DT);
+    assert_eq!(world.connections.get(connection_id).unwrap().health, 1.);
+}
@@ -0,0 +1,144 @@
+// Saving and loading a world should let the simulation continue exactly as if it was never interrupted
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+fn create_world (seed: u64) -> World {
+    let mut world = World::new(WorldSize::default(), SimSettings::default(), seed);
+    world.add_test_data();
+    world
+}
+
+fn get_snapshot_bytes (world: &World) -> Vec<u8> {
+    let mut writer = SnapshotWriter::new();
+    world.write_snapshot(&mut writer);
+    writer.output
+}
+
+// each test uses its own file so the tests can run in parallel
+fn get_temp_path (name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cell_engine_test_{}_{name}.snapshot", std::process::id()))
+}
+
+// a saved world that has been running for a while, so that it isn't just the starting layout
+fn get_saved_world_data (path: &Path) -> Vec<u8> {
+    let mut world = create_world(0);
+    for _ in 0..50 {
+        world.step(DT);
+    }
+    save_world(&world, &Camera::new(), path).unwrap();
+    fs::read(path).unwrap()
+}
+
+
+
+
+
+#[test]
+fn round_trip_continues_identically() {
+    let (ticks_before_save, ticks_after_load) = (100, 100);
+    let path = get_temp_path("round_trip");
+
+    let mut uninterrupted_world = create_world(1);
+    for _ in 0..(ticks_before_save + ticks_after_load) {
+        uninterrupted_world.step(DT);
+    }
+
+    let mut saved_world = create_world(1);
+    for _ in 0..ticks_before_save {
+        saved_world.step(DT);
+    }
+    save_world(&saved_world, &Camera::new(), &path).unwrap();
+    let (mut loaded_world, _) = load_world(&path, SimSettings::default()).unwrap();
+    let _ = fs::remove_file(&path);
+    assert!(get_snapshot_bytes(&loaded_world) == get_snapshot_bytes(&saved_world), "the loaded world doesn't match the saved world");
+    for _ in 0..ticks_after_load {
+        loaded_world.step(DT);
+    }
+
+    assert!(get_snapshot_bytes(&loaded_world) == get_snapshot_bytes(&uninterrupted_world), "the loaded world diverged from the uninterrupted world");
+}
+
+#[test]
+fn camera_round_trip() {
+    let path = get_temp_path("camera");
+    let camera = Camera {x: 12.5, y: -3., zoom: 0.75};
+    save_world(&create_world(0), &camera, &path).unwrap();
+    let (_, loaded_camera) = load_world(&path, SimSettings::default()).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!((loaded_camera.x, loaded_camera.y, loaded_camera.zoom), (camera.x, camera.y, camera.zoom));
+}
+
+#[test]
+fn loaded_cells_share_their_genome() {
+    let path = get_temp_path("genome_table");
+    let world = create_world(0);
+    save_world(&world, &Camera::new(), &path).unwrap();
+    let (loaded_world, _) = load_world(&path, SimSettings::default()).unwrap();
+    let _ = fs::remove_file(&path);
+    let mut genomes = loaded_world.cells.master_list.iter().filter_map(|entity_data| entity_data.0.as_ref()?.genome.clone());
+    let first_genome = genomes.next().unwrap();
+    assert!(genomes.all(|genome| Arc::ptr_eq(&genome, &first_genome)), "every test triad should share one genome after loading");
+}
+
+#[test]
+fn world_size_round_trip() {
+    let path = get_temp_path("world_size");
+    let size = WorldSize {max_entities_count: 500, ..WorldSize::new(40, 25)};
+    let mut world = World::new(size, SimSettings::default(), 0);
+    world.add_test_data();
+    save_world(&world, &Camera::new(), &path).unwrap();
+    let (loaded_world, _) = load_world(&path, SimSettings::default()).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(loaded_world.size, size);
+    assert_eq!((loaded_world.cells.size, loaded_world.food.size), (size, size));
+    assert!(get_snapshot_bytes(&loaded_world) == get_snapshot_bytes(&world));
+}
+
+#[test]
+fn truncated_file_is_an_error() {
+    let path = get_temp_path("truncated");
+    let data = get_saved_world_data(&path);
+    let lengths = (0..data.len()).step_by(data.len() / 100 + 1).chain([data.len() - 1]);
+    for len in lengths {
+        fs::write(&path, &data[..len]).unwrap();
+        assert!(matches!(load_world(&path, SimSettings::default()), Err(ProgramError::InvalidSnapshot (_))), "a file truncated to {len} bytes was accepted");
+    }
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn corrupt_file_is_an_error_or_a_valid_world() {
+    let path = get_temp_path("corrupt");
+    let data = get_saved_world_data(&path);
+    // overwrite bytes one at a time with values that are likely to be out of range, none of these should panic
+    let mut rng = SimRng::new(0);
+    for _ in 0..100 {
+        let mut corrupt_data = data.clone();
+        let index = rng.range_usize(corrupt_data.len());
+        corrupt_data[index] = [0x00, 0x7f, 0x80, 0xff][rng.range_usize(4)];
+        fs::write(&path, &corrupt_data).unwrap();
+        if let Ok((mut loaded_world, _)) = load_world(&path, SimSettings::default()) {
+            loaded_world.step(DT);
+        }
+    }
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn other_files_are_rejected() {
+    let path = get_temp_path("not_a_snapshot");
+    fs::write(&path, "not a snapshot\n").unwrap();
+    let result = load_world(&path, SimSettings::default());
+    let _ = fs::remove_file(&path);
+    assert!(matches!(result, Err(ProgramError::InvalidSnapshot (_))));
+}
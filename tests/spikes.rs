@@ -0,0 +1,64 @@
+// Spike cells damage the touching cells of other organisms, paying energy for the damage they deal
+
+
+
+use cell_engine::prelude::*;
+
+
+
+const DT: f64 = 0.016;
+
+
+
+
+
+// a spike cell with `spike_energy` touching a mouth cell (which does nothing without food) on its right
+fn create_spike_world (spike_energy: f64) -> (World, EntityID, EntityID) {
+    let settings = SimSettings {warmup_frames: 0, cell_energy_use_rate: 0., ..SimSettings::default()};
+    let mut world = World::new(WorldSize::new(20, 20), settings, 0);
+    let spike_cell = RawCell::new_spike_cell(&world.settings);
+    let spike_id = world.cells.add_entity(Cell::new(spike_cell, 10., 10., 1., spike_energy, 0.)).unwrap();
+    let victim_id = world.cells.add_entity(Cell::new(RawCell::new_mouth_cell(), 10.8, 10., 1., 0., 0.)).unwrap();
+    (world, spike_id, victim_id)
+}
+
+// organisms are found at the end of the first step, so spikes only start telling organisms apart on the second one
+fn step_twice (world: &mut World) {
+    world.step(DT);
+    world.step(DT);
+}
+
+
+
+
+
+#[test]
+fn spikes_damage_other_organisms() {
+    let (mut world, spike_id, victim_id) = create_spike_world(1.);
+
+    step_twice(&mut world);
+
+    let damage = world.settings.spike_damage.default * DT;
+    assert!((world.cells.get(victim_id).unwrap().health - (1. - damage)).abs() < 1e-9);
+    let spike_energy = world.cells.get(spike_id).unwrap().energy;
+    assert!((spike_energy - (1. - damage * world.settings.spike_energy_cost)).abs() < 1e-9);
+}
+
+#[test]
+fn spikes_leave_their_own_organism_alone() {
+    let (mut world, spike_id, victim_id) = create_spike_world(1.);
+    world.connect_cells(Connection::new(spike_id, victim_id));
+
+    step_twice(&mut world);
+
+    assert_eq!(world.cells.get(victim_id).unwrap().health, 1.);
+}
+
+#[test]
+fn spikes_need_energy() {
+    let (mut world, _, victim_id) = create_spike_world(0.);
+
+    step_twice(&mut world);
+
+    assert_eq!(world.cells.get(victim_id).unwrap().health, 1.);
+}